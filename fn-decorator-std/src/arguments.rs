@@ -0,0 +1,75 @@
+use std::{
+    fmt,
+    sync::{Mutex, TryLockError},
+};
+
+/// The names and the values of the arguments of a decorated function. Decorator functions used
+/// with the `with_arguments` option receive it before the decorated function.
+pub type Arguments<'a> = [(&'static str, &'a dyn fmt::Debug)];
+
+/// An argument of a decorated function, which can be printed by the decorator function until the
+/// decorated function takes it.
+pub struct Argument<T> {
+    value: Mutex<Option<T>>,
+}
+
+impl<T> Argument<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value: Mutex::new(Some(value)),
+        }
+    }
+
+    /// Takes the value out of the argument.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value was already taken.
+    pub fn take(&self) -> T {
+        self.value
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .expect("the argument was already taken")
+    }
+}
+
+impl<T: Clone> Argument<T> {
+    /// Clones the value of the argument, so it can be passed to the decorated function more than
+    /// once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value was already taken.
+    pub fn cloned(&self) -> T {
+        self.value
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .expect("the argument was already taken")
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Argument<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self.value.try_lock() {
+            Ok(value) => value,
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+            Err(TryLockError::WouldBlock) => return f.write_str("<locked>"),
+        };
+
+        match value.as_ref() {
+            Some(value) => value.fmt(f),
+            None => f.write_str("<moved>"),
+        }
+    }
+}
+
+/// Formats a call of the decorated function, e.g. `transfer(from = 1, amount = 10)`.
+pub fn format_call(name: &str, arguments: &Arguments) -> String {
+    let arguments = arguments
+        .iter()
+        .map(|(name, value)| format!("{name} = {value:?}"))
+        .collect::<Vec<_>>();
+    format!("{name}({})", arguments.join(", "))
+}
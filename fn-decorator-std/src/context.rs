@@ -0,0 +1,22 @@
+use std::fmt;
+
+/// Metadata of a decorated function. Decorator functions used with the `with_context` option
+/// receive it as their first argument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FnContext {
+    /// The name of the decorated function.
+    pub name: &'static str,
+    /// The path of the module the decorated function is declared in.
+    pub module_path: &'static str,
+    pub file: &'static str,
+    pub line: u32,
+    pub is_async: bool,
+    /// Whether the decorated function has a `self` parameter.
+    pub is_method: bool,
+}
+
+impl fmt::Display for FnContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::{}", self.module_path, self.name)
+    }
+}
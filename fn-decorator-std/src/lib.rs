@@ -0,0 +1,36 @@
+#![forbid(unsafe_code)]
+
+//! Ready-made decorator functions for the `use_decorator` and `use_impl_decorator` macros of the
+//! `fn-decorator` crate.
+//!
+//! The decorators receive the decorated function as a callable without parameters, so they can be
+//! used with functions of any signature by giving `exact_parameters = []` to the macro.
+//!
+//! Decorators that need to see or change the arguments can be written against the [`Next`]
+//! handle with the `decorator` attribute, and used with `pack_parameters`.
+//!
+//! Decorators used with the `with_context` option receive the [`FnContext`] of the decorated
+//! function as their first argument, and the ones used with the `with_arguments` option receive
+//! the [`Arguments`] of the decorated function.
+
+mod arguments;
+mod context;
+mod memoize;
+mod next;
+mod panic;
+mod retry;
+#[cfg(feature = "tracing")]
+mod span;
+mod switch;
+mod timing;
+
+pub use arguments::{format_call, Argument, Arguments};
+pub use context::FnContext;
+pub use memoize::{memoize, memoize_async, Memoize};
+pub use next::Next;
+pub use panic::{catch_unwind, panic_message};
+pub use retry::{retry, retry_async, retry_with_args, retry_with_args_async, Backoff};
+#[cfg(feature = "tracing")]
+pub use span::{in_span, in_span_async};
+pub use switch::{is_enabled, set_enabled, switch_names, Switch};
+pub use timing::{elapsed, elapsed_async};
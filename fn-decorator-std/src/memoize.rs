@@ -0,0 +1,78 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    hash::Hash,
+    sync::{Mutex, OnceLock},
+};
+
+/// Cache of the results of a memoized function, meant to be stored in a `static`.
+pub struct Memoize<K, V> {
+    results: OnceLock<Mutex<HashMap<K, V>>>,
+}
+
+impl<K, V> Memoize<K, V> {
+    pub const fn new() -> Self {
+        Self {
+            results: OnceLock::new(),
+        }
+    }
+}
+
+impl<K, V> Default for Memoize<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memoize<K, V> {
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.results().lock().unwrap().get(key).cloned()
+    }
+
+    pub fn insert(&self, key: K, value: V) {
+        self.results().lock().unwrap().insert(key, value);
+    }
+
+    pub fn clear(&self) {
+        self.results().lock().unwrap().clear();
+    }
+
+    fn results(&self) -> &Mutex<HashMap<K, V>> {
+        self.results.get_or_init(Default::default)
+    }
+}
+
+/// Returns the cached result for `key`, or calls the decorated function and caches its result.
+///
+/// The decorated function must have exactly one parameter that is passed to the decorator, e.g.
+/// with `exact_parameters = [key]`, or its parameters have to be packed into one tuple key with
+/// `pack_parameters`.
+pub fn memoize<K: Hash + Eq + Clone, V: Clone>(
+    cache: &Memoize<K, V>,
+    f: impl FnOnce(K) -> V,
+    key: K,
+) -> V {
+    if let Some(value) = cache.get(&key) {
+        return value;
+    }
+
+    let value = f(key.clone());
+    cache.insert(key, value.clone());
+    value
+}
+
+/// Returns the cached result for `key`, or awaits the decorated async function and caches its
+/// result. The cache is not locked while the decorated function runs.
+pub async fn memoize_async<K: Hash + Eq + Clone, V: Clone, FutureType: Future<Output = V>>(
+    cache: &Memoize<K, V>,
+    f: impl FnOnce(K) -> FutureType,
+    key: K,
+) -> V {
+    if let Some(value) = cache.get(&key) {
+        return value;
+    }
+
+    let value = f(key.clone()).await;
+    cache.insert(key, value.clone());
+    value
+}
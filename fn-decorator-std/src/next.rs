@@ -0,0 +1,44 @@
+use std::marker::PhantomData;
+
+/// Handle of the decorated function together with its packed arguments.
+///
+/// Decorator functions defined with the `decorator` attribute of the `fn-decorator` crate receive
+/// it as their last parameter, when the decorated function uses `pack_parameters`. The `Args` type
+/// is the tuple of the arguments and `R` is the return type of the decorated function.
+pub struct Next<Args, R, F = fn(Args) -> R> {
+    f: F,
+    args: Args,
+    return_type: PhantomData<fn() -> R>,
+}
+
+impl<Args, R, F: FnOnce(Args) -> R> Next<Args, R, F> {
+    pub fn new(f: F, args: Args) -> Self {
+        Self {
+            f,
+            args,
+            return_type: PhantomData,
+        }
+    }
+
+    pub fn args(&self) -> &Args {
+        &self.args
+    }
+
+    pub fn args_mut(&mut self) -> &mut Args {
+        &mut self.args
+    }
+
+    /// Calls the decorated function with the packed arguments.
+    pub fn call(self) -> R {
+        (self.f)(self.args)
+    }
+
+    /// Calls the decorated function with the given arguments instead of the packed ones.
+    pub fn call_with(self, args: Args) -> R {
+        (self.f)(args)
+    }
+
+    pub fn into_parts(self) -> (F, Args) {
+        (self.f, self.args)
+    }
+}
@@ -0,0 +1,21 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+};
+
+/// Calls the decorated function, and converts a panic into the value returned by `on_panic`, e.g.
+/// into an error code of a function exported to C. `on_panic` receives the payload of the panic.
+pub fn catch_unwind<R>(
+    on_panic: impl FnOnce(Box<dyn Any + Send>) -> R,
+    f: impl FnOnce() -> R,
+) -> R {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(on_panic)
+}
+
+/// The message of a panic payload, if the panic was raised with a message.
+pub fn panic_message(payload: &(dyn Any + Send)) -> Option<&str> {
+    payload
+        .downcast_ref::<&'static str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+}
@@ -0,0 +1,133 @@
+use std::{future::Future, time::Duration};
+
+/// Describes how many times and how often a failing function is retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    max_attempts: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    factor: u32,
+}
+
+impl Backoff {
+    /// Waits the same `delay` between the attempts.
+    pub const fn fixed(max_attempts: u32, delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            initial_delay: delay,
+            max_delay: delay,
+            factor: 1,
+        }
+    }
+
+    /// Doubles the delay after every failed attempt, starting from `initial_delay`.
+    pub const fn exponential(max_attempts: u32, initial_delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            initial_delay,
+            max_delay: Duration::MAX,
+            factor: 2,
+        }
+    }
+
+    /// Limits the delay between two attempts.
+    pub const fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Multiplies the delay with `factor` after every failed attempt.
+    pub const fn with_factor(mut self, factor: u32) -> Self {
+        self.factor = factor;
+        self
+    }
+
+    /// The delay after the given number of failed attempts.
+    pub fn delay(&self, failed_attempts: u32) -> Duration {
+        let mut delay = self.initial_delay;
+        for _ in 1..failed_attempts {
+            delay = delay.saturating_mul(self.factor);
+            if delay >= self.max_delay {
+                break;
+            }
+        }
+        delay.min(self.max_delay)
+    }
+}
+
+/// Calls the decorated function until it returns `Ok` or the attempts run out, then returns the
+/// last result. The thread sleeps between two attempts.
+///
+/// The decorated function is called more than once, so the parameters it receives have to be
+/// `Copy`, or the `reusable` option has to be given.
+pub fn retry<T, E>(backoff: Backoff, mut f: impl FnMut() -> Result<T, E>) -> Result<T, E> {
+    let mut attempt = 0;
+    loop {
+        let result = f();
+        attempt += 1;
+        if result.is_ok() || attempt >= backoff.max_attempts {
+            return result;
+        }
+
+        std::thread::sleep(backoff.delay(attempt));
+    }
+}
+
+/// Awaits the decorated async function until it returns `Ok` or the attempts run out, then
+/// returns the last result. The future returned by `sleep` is awaited between two attempts, so
+/// the decorator works with any async runtime (e.g. `tokio::time::sleep`).
+///
+/// The decorated function is called more than once, so the parameters it receives have to be
+/// `Copy`, or the `reusable` option has to be given.
+pub async fn retry_async<T, E, FutureType, SleepFutureType>(
+    backoff: Backoff,
+    sleep: impl Fn(Duration) -> SleepFutureType,
+    mut f: impl FnMut() -> FutureType,
+) -> Result<T, E>
+where
+    FutureType: Future<Output = Result<T, E>>,
+    SleepFutureType: Future<Output = ()>,
+{
+    let mut attempt = 0;
+    loop {
+        let result = f().await;
+        attempt += 1;
+        if result.is_ok() || attempt >= backoff.max_attempts {
+            return result;
+        }
+
+        sleep(backoff.delay(attempt)).await;
+    }
+}
+
+/// Calls the decorated function with a clone of `args` until it returns `Ok` or the attempts run
+/// out, then returns the last result. The thread sleeps between two attempts.
+///
+/// It is meant to be used with `pack_parameters`, so the parameters the decorated function passes
+/// to the decorator are received as one tuple, and they have to be `Clone`.
+pub fn retry_with_args<Args: Clone, T, E>(
+    backoff: Backoff,
+    mut f: impl FnMut(Args) -> Result<T, E>,
+    args: Args,
+) -> Result<T, E> {
+    retry(backoff, || f(args.clone()))
+}
+
+/// Awaits the decorated async function with a clone of `args` until it returns `Ok` or the
+/// attempts run out, then returns the last result. The future returned by `sleep` is awaited
+/// between two attempts.
+///
+/// It is meant to be used with `pack_parameters`, so the parameters the decorated function passes
+/// to the decorator are received as one tuple, and they have to be `Clone`.
+pub async fn retry_with_args_async<Args: Clone, T, E, FutureType, SleepFutureType>(
+    backoff: Backoff,
+    sleep: impl Fn(Duration) -> SleepFutureType,
+    mut f: impl FnMut(Args) -> FutureType,
+    args: Args,
+) -> Result<T, E>
+where
+    FutureType: Future<Output = Result<T, E>>,
+    SleepFutureType: Future<Output = ()>,
+{
+    retry_async(backoff, sleep, || f(args.clone())).await
+}
@@ -0,0 +1,17 @@
+use std::future::Future;
+
+use tracing::{Instrument, Span};
+
+/// Runs the decorated function inside `span`.
+pub fn in_span<R>(span: Span, f: impl FnOnce() -> R) -> R {
+    let _entered = span.enter();
+    f()
+}
+
+/// Instruments the future of the decorated async function with `span`.
+pub async fn in_span_async<R, FutureType: Future<Output = R>>(
+    span: Span,
+    f: impl FnOnce() -> FutureType,
+) -> R {
+    f().instrument(span).await
+}
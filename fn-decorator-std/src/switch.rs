@@ -0,0 +1,81 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, OnceLock,
+    },
+};
+
+use crate::FnContext;
+
+/// The states of the switches by their names. The states are leaked, so the switches can keep a
+/// reference to them, and they are created by the first use or change of a name.
+static REGISTRY: Mutex<BTreeMap<String, &'static AtomicBool>> = Mutex::new(BTreeMap::new());
+
+fn registered_state(name: &str) -> &'static AtomicBool {
+    let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(state) = registry.get(name) {
+        return state;
+    }
+
+    let state = Box::leak(Box::new(AtomicBool::new(true)));
+    registry.insert(name.into(), state);
+    state
+}
+
+/// Enables or disables the decorators having the switch of the given name. The switch of a
+/// decorated function is called `<module path>::<function name>` if it is not named explicitly,
+/// the switches of methods are always named explicitly.
+pub fn set_enabled(name: &str, enabled: bool) {
+    registered_state(name).store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the decorators having the switch of the given name are enabled. Switches are enabled
+/// until they are disabled.
+pub fn is_enabled(name: &str) -> bool {
+    registered_state(name).load(Ordering::Relaxed)
+}
+
+/// The names of the switches that have been used or changed.
+pub fn switch_names() -> Vec<String> {
+    let registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    registry.keys().cloned().collect()
+}
+
+enum SwitchName {
+    Name(&'static str),
+    Fn(&'static FnContext),
+}
+
+/// The runtime switch of a decorator, generated by the `switch` option. The state is looked up in
+/// the registry on the first check, the further checks load an atomic boolean.
+pub struct Switch {
+    name: SwitchName,
+    state: OnceLock<&'static AtomicBool>,
+}
+
+impl Switch {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name: SwitchName::Name(name),
+            state: OnceLock::new(),
+        }
+    }
+
+    /// The switch named after the decorated function.
+    pub const fn for_fn(context: &'static FnContext) -> Self {
+        Self {
+            name: SwitchName::Fn(context),
+            state: OnceLock::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.state
+            .get_or_init(|| match self.name {
+                SwitchName::Name(name) => registered_state(name),
+                SwitchName::Fn(context) => registered_state(&context.to_string()),
+            })
+            .load(Ordering::Relaxed)
+    }
+}
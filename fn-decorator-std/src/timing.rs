@@ -0,0 +1,23 @@
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+/// Measures the time spent in the decorated function and reports it to `on_elapsed`.
+pub fn elapsed<R>(on_elapsed: impl FnOnce(Duration), f: impl FnOnce() -> R) -> R {
+    let start = Instant::now();
+    let result = f();
+    on_elapsed(start.elapsed());
+    result
+}
+
+/// Measures the time spent in the decorated async function and reports it to `on_elapsed`.
+pub async fn elapsed_async<R, FutureType: Future<Output = R>>(
+    on_elapsed: impl FnOnce(Duration),
+    f: impl FnOnce() -> FutureType,
+) -> R {
+    let start = Instant::now();
+    let result = f().await;
+    on_elapsed(start.elapsed());
+    result
+}
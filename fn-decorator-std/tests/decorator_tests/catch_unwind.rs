@@ -0,0 +1,26 @@
+use std::sync::Mutex;
+
+use fn_decorator::use_decorator;
+use fn_decorator_std::{catch_unwind, panic_message};
+
+static PANIC_MESSAGE: Mutex<String> = Mutex::new(String::new());
+
+fn log_panic(payload: Box<dyn std::any::Any + Send>) -> i32 {
+    *PANIC_MESSAGE.lock().unwrap() = panic_message(&*payload).unwrap_or_default().into();
+    -1
+}
+
+#[use_decorator(catch_unwind(log_panic), exact_parameters = [])]
+extern "C" fn checked_div(x: i32, y: i32) -> i32 {
+    if y == 0 {
+        panic!("division of {x} by zero");
+    }
+    x / y
+}
+
+#[test]
+fn catch_unwind_decorator() {
+    assert_eq!(checked_div(6, 3), 2);
+    assert_eq!(checked_div(6, 0), -1);
+    assert_eq!(*PANIC_MESSAGE.lock().unwrap(), "division of 6 by zero");
+}
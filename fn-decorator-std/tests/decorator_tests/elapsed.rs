@@ -0,0 +1,47 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use fn_decorator::{use_decorator, use_impl_decorator};
+use fn_decorator_std::{elapsed, elapsed_async};
+
+static SLEEP_MEASURED: AtomicBool = AtomicBool::new(false);
+static ASYNC_SLEEP_MEASURED: AtomicBool = AtomicBool::new(false);
+
+#[use_decorator(
+    elapsed(|elapsed| SLEEP_MEASURED.store(elapsed >= Duration::from_millis(10), Ordering::SeqCst)),
+    exact_parameters = [],
+)]
+fn sleep_then_double(x: i64) -> i64 {
+    std::thread::sleep(Duration::from_millis(10));
+    x * 2
+}
+
+struct MyStruct {
+    x: i64,
+}
+
+impl MyStruct {
+    #[use_impl_decorator(
+        elapsed_async(|elapsed| ASYNC_SLEEP_MEASURED.store(elapsed >= Duration::from_millis(10), Ordering::SeqCst)),
+        exact_parameters = [],
+    )]
+    async fn sleep_then_add(&self, y: i64) -> i64 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        self.x + y
+    }
+}
+
+#[test]
+fn elapsed_decorator() {
+    assert_eq!(sleep_then_double(2), 4);
+    assert!(SLEEP_MEASURED.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn elapsed_async_decorator() {
+    let obj = MyStruct { x: 1 };
+    assert_eq!(obj.sleep_then_add(1).await, 2);
+    assert!(ASYNC_SLEEP_MEASURED.load(Ordering::SeqCst));
+}
@@ -0,0 +1,54 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use fn_decorator::use_decorator;
+use fn_decorator_std::{memoize, memoize_async, Memoize};
+
+static SQUARE_CACHE: Memoize<i64, i64> = Memoize::new();
+static SQUARE_CALLS: AtomicU32 = AtomicU32::new(0);
+
+static GREET_CACHE: Memoize<String, String> = Memoize::new();
+static GREET_CALLS: AtomicU32 = AtomicU32::new(0);
+
+static ADD_CACHE: Memoize<(i64, i64), i64> = Memoize::new();
+static ADD_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[use_decorator(memoize(&SQUARE_CACHE))]
+fn square(x: i64) -> i64 {
+    SQUARE_CALLS.fetch_add(1, Ordering::SeqCst);
+    x * x
+}
+
+#[use_decorator(memoize_async(&GREET_CACHE), exact_parameters = [name])]
+async fn greet(greeting: &str, name: String) -> String {
+    GREET_CALLS.fetch_add(1, Ordering::SeqCst);
+    format!("{greeting} {name}")
+}
+
+#[use_decorator(memoize(&ADD_CACHE), pack_parameters)]
+fn add(left: i64, right: i64) -> i64 {
+    ADD_CALLS.fetch_add(1, Ordering::SeqCst);
+    left + right
+}
+
+#[test]
+fn memoize_decorator() {
+    assert_eq!(square(3), 9);
+    assert_eq!(square(3), 9);
+    assert_eq!(square(4), 16);
+    assert_eq!(SQUARE_CALLS.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn memoize_async_decorator() {
+    assert_eq!(greet("hello", "world".into()).await, "hello world");
+    assert_eq!(greet("bye", "world".into()).await, "hello world");
+    assert_eq!(GREET_CALLS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn memoize_packed_parameters_decorator() {
+    assert_eq!(add(1, 2), 3);
+    assert_eq!(add(1, 2), 3);
+    assert_eq!(add(2, 1), 3);
+    assert_eq!(ADD_CALLS.load(Ordering::SeqCst), 2);
+}
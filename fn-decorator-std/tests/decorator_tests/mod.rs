@@ -0,0 +1,5 @@
+mod catch_unwind;
+mod elapsed;
+mod memoize;
+mod retry;
+mod span;
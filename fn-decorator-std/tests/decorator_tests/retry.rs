@@ -0,0 +1,98 @@
+use std::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+
+use fn_decorator::use_decorator;
+use fn_decorator_std::{retry, retry_async, retry_with_args, retry_with_args_async, Backoff};
+
+static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+static ASYNC_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+static ARGS_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+static ASYNC_ARGS_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+
+#[use_decorator(retry(Backoff::fixed(3, Duration::from_millis(1))), exact_parameters = [])]
+fn fail_twice(x: i64) -> Result<i64, u32> {
+    let attempt = ATTEMPTS.fetch_add(1, Ordering::SeqCst) + 1;
+    if attempt < 3 {
+        Err(attempt)
+    } else {
+        Ok(x)
+    }
+}
+
+#[use_decorator(
+    retry_async(Backoff::exponential(2, Duration::from_millis(1)), tokio::time::sleep),
+    exact_parameters = [],
+)]
+async fn always_fail(x: i64) -> Result<(), i64> {
+    ASYNC_ATTEMPTS.fetch_add(1, Ordering::SeqCst);
+    Err(x)
+}
+
+#[use_decorator(
+    retry_with_args(Backoff::fixed(3, Duration::from_millis(1))),
+    pack_parameters
+)]
+fn concat_on_second_attempt(left: String, right: String) -> Result<String, u32> {
+    let attempt = ARGS_ATTEMPTS.fetch_add(1, Ordering::SeqCst) + 1;
+    if attempt < 2 {
+        Err(attempt)
+    } else {
+        Ok(left + &right)
+    }
+}
+
+#[use_decorator(
+    retry_with_args_async(Backoff::fixed(2, Duration::from_millis(1)), tokio::time::sleep),
+    pack_parameters,
+    exact_parameters = [name],
+)]
+async fn greet_on_second_attempt(greeting: &'static str, name: String) -> Result<String, u32> {
+    let attempt = ASYNC_ARGS_ATTEMPTS.fetch_add(1, Ordering::SeqCst) + 1;
+    if attempt < 2 {
+        Err(attempt)
+    } else {
+        Ok(format!("{greeting} {name}"))
+    }
+}
+
+#[test]
+fn retry_decorator() {
+    assert_eq!(fail_twice(5), Ok(5));
+    assert_eq!(ATTEMPTS.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn retry_async_decorator() {
+    assert_eq!(always_fail(5).await, Err(5));
+    assert_eq!(ASYNC_ATTEMPTS.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn retry_with_args_decorator() {
+    assert_eq!(
+        concat_on_second_attempt("left".into(), "right".into()),
+        Ok("leftright".into())
+    );
+    assert_eq!(ARGS_ATTEMPTS.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn retry_with_args_async_decorator() {
+    assert_eq!(
+        greet_on_second_attempt("hello", "world".into()).await,
+        Ok("hello world".into())
+    );
+    assert_eq!(ASYNC_ARGS_ATTEMPTS.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn backoff_delays() {
+    let backoff = Backoff::exponential(5, Duration::from_millis(10))
+        .with_max_delay(Duration::from_millis(30));
+    assert_eq!(backoff.delay(1), Duration::from_millis(10));
+    assert_eq!(backoff.delay(2), Duration::from_millis(20));
+    assert_eq!(backoff.delay(3), Duration::from_millis(30));
+    assert_eq!(backoff.delay(4), Duration::from_millis(30));
+}
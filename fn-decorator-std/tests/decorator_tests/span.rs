@@ -0,0 +1,96 @@
+#![cfg(feature = "tracing")]
+
+use std::sync::{Arc, Mutex};
+
+use fn_decorator::use_decorator;
+use fn_decorator_std::{in_span, in_span_async};
+use tracing::{
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+/// Records the name of the innermost entered span for every event.
+#[derive(Default)]
+struct SpanRecorder {
+    span_names: Mutex<Vec<&'static str>>,
+    entered_spans: Mutex<Vec<Id>>,
+    event_spans: Mutex<Vec<Option<&'static str>>>,
+}
+
+impl Subscriber for SpanRecorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut span_names = self.span_names.lock().unwrap();
+        span_names.push(span.metadata().name());
+        Id::from_u64(span_names.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {
+        let span_name = self
+            .entered_spans
+            .lock()
+            .unwrap()
+            .last()
+            .map(|id| self.span_names.lock().unwrap()[id.into_u64() as usize - 1]);
+        self.event_spans.lock().unwrap().push(span_name);
+    }
+
+    fn enter(&self, span: &Id) {
+        self.entered_spans.lock().unwrap().push(span.clone());
+    }
+
+    fn exit(&self, span: &Id) {
+        let mut entered_spans = self.entered_spans.lock().unwrap();
+        if let Some(index) = entered_spans.iter().rposition(|entered| entered == span) {
+            entered_spans.remove(index);
+        }
+    }
+}
+
+#[use_decorator(in_span(tracing::info_span!("double")), exact_parameters = [])]
+fn double(x: i64) -> i64 {
+    tracing::info!("doubling");
+    x * 2
+}
+
+#[use_decorator(in_span_async(tracing::info_span!("triple")), exact_parameters = [])]
+async fn triple(x: i64) -> i64 {
+    tokio::task::yield_now().await;
+    tracing::info!("tripling");
+    x * 3
+}
+
+#[test]
+fn in_span_decorator() {
+    let recorder = Arc::new(SpanRecorder::default());
+    tracing::subscriber::with_default(recorder.clone(), || {
+        assert_eq!(double(2), 4);
+        tracing::info!("done");
+    });
+
+    assert_eq!(
+        *recorder.event_spans.lock().unwrap(),
+        [Some("double"), None]
+    );
+}
+
+#[tokio::test]
+async fn in_span_async_decorator() {
+    let recorder = Arc::new(SpanRecorder::default());
+    // the test runtime polls the future on this thread, so the default subscriber applies to it
+    let _default = tracing::subscriber::set_default(recorder.clone());
+    assert_eq!(triple(2).await, 6);
+    tracing::info!("done");
+
+    assert_eq!(
+        *recorder.event_spans.lock().unwrap(),
+        [Some("triple"), None]
+    );
+}
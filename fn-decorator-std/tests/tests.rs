@@ -0,0 +1 @@
+mod decorator_tests;
@@ -1,14 +1,20 @@
 #![forbid(unsafe_code)]
 #![allow(clippy::redundant_clone)]
 
+use std::hash::{Hash, Hasher};
+
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
-use quote::{quote, ToTokens};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::{
-    bracketed, parenthesized,
-    parse::{discouraged::Speculative, Parse},
+    bracketed,
+    ext::IdentExt,
+    parenthesized,
+    parse::Parse,
     punctuated::Punctuated,
-    Expr, FnArg, ImplItemFn, Pat, Path, Token,
+    visit_mut::{self, VisitMut},
+    Attribute, Expr, FnArg, ForeignItemFn, GenericParam, ImplItem, ImplItemFn, ItemFn, ItemImpl,
+    Pat, Path, Signature, Token, TraitItemFn, Visibility,
 };
 
 fn read_exact_ident<'a>(
@@ -27,6 +33,7 @@ fn read_exact_ident<'a>(
     Ok(ident_name)
 }
 
+#[derive(Clone)]
 struct DecoratorFunctionCall {
     middleware_fn_path: Path,
     middleware_params: Punctuated<Expr, Token![,]>,
@@ -49,21 +56,22 @@ impl Parse for DecoratorFunctionCall {
     }
 }
 
-struct ParameterName(String);
+struct ParameterName(Ident);
 
 impl Parse for ParameterName {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         Ok(Self(input.step(|cursor| {
             if let Some((ident, rest)) = cursor.ident() {
-                Ok((ident.to_string(), rest))
+                Ok((ident, rest))
             } else {
-                Err(cursor.error("expected identifier"))
+                Err(cursor.error("expected parameter name"))
             }
         })?))
     }
 }
 
-struct HideParametersList(Vec<String>);
+#[derive(Clone)]
+struct HideParametersList(Vec<Ident>);
 
 impl Parse for HideParametersList {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -81,7 +89,8 @@ impl Parse for HideParametersList {
     }
 }
 
-struct ExactParametersList(Vec<String>);
+#[derive(Clone)]
+struct ExactParametersList(Vec<Ident>);
 
 impl Parse for ExactParametersList {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -99,11 +108,22 @@ impl Parse for ExactParametersList {
     }
 }
 
+#[derive(Clone)]
 enum ParametersOverride {
     Exact(ExactParametersList),
     Hide(HideParametersList),
 }
 
+impl ParametersOverride {
+    fn names(&self) -> &[Ident] {
+        match self {
+            ParametersOverride::Exact(exact_parameters_list) => &exact_parameters_list.0,
+            ParametersOverride::Hide(hide_parameters_list) => &hide_parameters_list.0,
+        }
+    }
+}
+
+#[derive(Clone)]
 struct OverrideReturnType(syn::Type);
 
 impl Parse for OverrideReturnType {
@@ -122,104 +142,756 @@ impl ToTokens for OverrideReturnType {
     }
 }
 
-struct UseDecoratorArg {
-    debug: bool,
+/// Whether the wrapper of a decorator layer is an async function, and whether it awaits the
+/// decorator function.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DecoratorMode {
+    /// The wrapper is a function returning the result of the decorator function.
+    Sync,
+    /// The wrapper is an async function awaiting the result of the decorator function.
+    Async,
+    /// The wrapper is a function returning the future returned by the decorator function.
+    Future,
+}
+
+impl Parse for DecoratorMode {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        read_exact_ident("mode", &input)?;
+        input.parse::<Token![=]>()?;
+        if input.parse::<Option<Token![async]>>()?.is_some() {
+            return Ok(DecoratorMode::Async);
+        }
+
+        let mode = input.parse::<Ident>()?;
+        if mode == "sync" {
+            Ok(DecoratorMode::Sync)
+        } else if mode == "future" {
+            Ok(DecoratorMode::Future)
+        } else {
+            Err(unknown_name_error(
+                &mode,
+                format!("unknown mode `{mode}`"),
+                ["async", "sync", "future"],
+            ))
+        }
+    }
+}
+
+#[derive(Clone)]
+struct DecoratorLayer {
     decorator_function_call: DecoratorFunctionCall,
     parameters_override: Option<ParametersOverride>,
+    pack_parameters: bool,
+    /// Whether the `FnContext` of the decorated function is passed as the first argument.
+    with_context: bool,
+    /// Whether the closure passed to the decorator function can be called more than once.
+    reusable: bool,
+    /// Whether the `Arguments` of the decorated function are passed before a callable without
+    /// parameters. The parameters override selects the arguments in this case.
+    with_arguments: bool,
+    /// The mode of the layer, it is async if the wrapped function is async by default.
+    mode: Option<DecoratorMode>,
+    override_return_type: Option<OverrideReturnType>,
+    /// The `cfg` predicate of the `when` option, the layer is only applied if it holds.
+    when: Option<syn::Meta>,
+    switch: Option<LayerSwitch>,
+}
+
+/// The runtime switch of a layer, the wrapped function is called directly when it is disabled.
+#[derive(Clone)]
+struct LayerSwitch {
+    span: Span,
+    /// The name of the switch, it is named after the decorated function by default.
+    name: Option<syn::LitStr>,
+}
+
+struct DecoratorLayerBuilder {
+    /// The span of the first argument of the layer, errors about the whole layer point to it.
+    span: Span,
+    decorator_function_call: Option<DecoratorFunctionCall>,
+    parameters_override: Option<ParametersOverride>,
+    /// The span of the `pack_parameters` argument.
+    pack_parameters: Option<Span>,
+    with_context: bool,
+    reusable: bool,
+    /// The span of the `with_arguments` argument.
+    with_arguments: Option<Span>,
+    mode: Option<DecoratorMode>,
     override_return_type: Option<OverrideReturnType>,
+    switch: Option<LayerSwitch>,
+}
+
+impl DecoratorLayerBuilder {
+    fn new(span: Span) -> Self {
+        Self {
+            span,
+            decorator_function_call: None,
+            parameters_override: None,
+            pack_parameters: None,
+            with_context: false,
+            reusable: false,
+            with_arguments: None,
+            mode: None,
+            override_return_type: None,
+            switch: None,
+        }
+    }
+
+    fn build(self) -> syn::Result<DecoratorLayer> {
+        if let (Some(pack_parameters), Some(_)) = (self.pack_parameters, self.with_arguments) {
+            return Err(syn::Error::new(
+                pack_parameters,
+                "`pack_parameters` cannot be given together with `with_arguments`",
+            ));
+        }
+
+        Ok(DecoratorLayer {
+            decorator_function_call: self.decorator_function_call.ok_or_else(|| {
+                syn::Error::new(
+                    self.span,
+                    "these options belong to the first decorator function call, but there is none",
+                )
+            })?,
+            parameters_override: self.parameters_override,
+            pack_parameters: self.pack_parameters.is_some(),
+            with_context: self.with_context,
+            reusable: self.reusable,
+            with_arguments: self.with_arguments.is_some(),
+            mode: self.mode,
+            override_return_type: self.override_return_type,
+            when: None,
+            switch: self.switch,
+        })
+    }
+}
+
+/// Decorator layers are listed from the outermost to the innermost one. Options like
+/// `hide_parameters` belong to the decorator function call they follow, options given before the
+/// first decorator function call belong to the first one.
+#[derive(Clone)]
+struct UseDecoratorArg {
+    debug: bool,
+    /// The span of the `assert_send` option, if it is given.
+    assert_send: Option<Span>,
+    layers: Vec<DecoratorLayer>,
 }
 
 impl Parse for UseDecoratorArg {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let mut override_return_type = None;
-        let mut parameter_override = None;
-        let mut decorator_function_call = None;
+        let use_decorator_arg = Self::parse_with(input, &[], |_| Ok(false))?;
+        if use_decorator_arg.layers.is_empty() {
+            return Err(input.error("at least one decorator function call is required"));
+        }
+
+        Ok(use_decorator_arg)
+    }
+}
+
+impl UseDecoratorArg {
+    const OPTIONS: [&'static str; 12] = [
+        "hide_parameters",
+        "exact_parameters",
+        "pack_parameters",
+        "with_context",
+        "reusable",
+        "with_arguments",
+        "mode",
+        "override_return_type",
+        "switch",
+        "debug",
+        "assert_send",
+        "when",
+    ];
+
+    /// Parses the arguments, `parse_extra_arg` is called with every option that is not a
+    /// decorator option, and it returns whether it consumed the option. `extra_options` are the
+    /// names of the options `parse_extra_arg` accepts. The returned list of layers can be empty.
+    fn parse_with(
+        input: syn::parse::ParseStream,
+        extra_options: &[&str],
+        mut parse_extra_arg: impl FnMut(syn::parse::ParseStream) -> syn::Result<bool>,
+    ) -> syn::Result<Self> {
+        let mut layer_builders: Vec<DecoratorLayerBuilder> = Vec::new();
         let mut debug = false;
+        let mut assert_send = None;
+        let mut when = None;
 
         let mut first_item = true;
 
         while !input.is_empty() {
             if !first_item {
                 input.parse::<Token![,]>()?;
+                if input.is_empty() {
+                    break;
+                }
+            }
+            first_item = false;
+
+            // a path followed by parentheses is a decorator function call, anything else is an
+            // option
+            let input_fork = input.fork();
+            let path = input_fork.parse::<Path>();
+            if path.is_ok() && input_fork.peek(syn::token::Paren) {
+                let parsed = input.parse::<DecoratorFunctionCall>()?;
+                match layer_builders.last_mut() {
+                    Some(layer) if layer.decorator_function_call.is_none() => {
+                        layer.decorator_function_call = Some(parsed);
+                    }
+                    _ => {
+                        let mut layer = DecoratorLayerBuilder::new(input.span());
+                        layer.decorator_function_call = Some(parsed);
+                        layer_builders.push(layer);
+                    }
+                }
+
+                continue;
             }
 
-            let input_fork_0 = input.fork();
-            let input_fork_1 = input.fork();
-            let input_fork_2 = input.fork();
-            let input_fork_3 = input.fork();
-            if let Ok(parsed) = input_fork_0.parse::<HideParametersList>() {
-                if parameter_override.is_some() {
-                    return Err(
-                        input.error("only one hide_parameters or exact_parameters list is allowed")
-                    );
+            let option = match path {
+                Ok(path) => match path.get_ident() {
+                    Some(option) => option.clone(),
+                    None => {
+                        return Err(syn::Error::new_spanned(
+                            path,
+                            "expected parentheses after the path of the decorator function",
+                        ))
+                    }
+                },
+                Err(_) => {
+                    return Err(input.error("expected a decorator function call or an option"))
+                }
+            };
+
+            let layer = current_layer_builder(&mut layer_builders, option.span());
+            if option == "hide_parameters" || option == "exact_parameters" {
+                if layer.parameters_override.is_some() {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "only one `hide_parameters` or `exact_parameters` list is allowed per decorator",
+                    ));
+                }
+
+                layer.parameters_override = Some(if option == "hide_parameters" {
+                    ParametersOverride::Hide(input.parse()?)
+                } else {
+                    ParametersOverride::Exact(input.parse()?)
+                });
+            } else if option == "pack_parameters" {
+                if layer.pack_parameters.is_some() {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "exactly one `pack_parameters` is allowed per decorator",
+                    ));
+                }
+
+                input.parse::<Ident>()?;
+                layer.pack_parameters = Some(option.span());
+            } else if option == "with_context" {
+                if layer.with_context {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "exactly one `with_context` is allowed per decorator",
+                    ));
+                }
+
+                input.parse::<Ident>()?;
+                layer.with_context = true;
+            } else if option == "reusable" {
+                if layer.reusable {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "exactly one `reusable` is allowed per decorator",
+                    ));
+                }
+
+                input.parse::<Ident>()?;
+                layer.reusable = true;
+            } else if option == "with_arguments" {
+                if layer.with_arguments.is_some() {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "exactly one `with_arguments` is allowed per decorator",
+                    ));
+                }
+
+                input.parse::<Ident>()?;
+                layer.with_arguments = Some(option.span());
+            } else if option == "mode" {
+                if layer.mode.is_some() {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "at most one `mode` is allowed per decorator",
+                    ));
                 }
 
-                parameter_override = Some(ParametersOverride::Hide(parsed));
-                input.advance_to(&input_fork_0);
-            } else if let Ok(parsed) = input_fork_0.parse::<ExactParametersList>() {
-                if parameter_override.is_some() {
-                    return Err(
-                        input.error("only one hide_parameters or exact_parameters list is allowed")
-                    );
+                layer.mode = Some(input.parse()?);
+            } else if option == "override_return_type" {
+                if layer.override_return_type.is_some() {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "at most one `override_return_type` is allowed per decorator",
+                    ));
                 }
 
-                parameter_override = Some(ParametersOverride::Exact(parsed));
-                input.advance_to(&input_fork_0);
-            } else if let Ok(parsed) = input_fork_1.parse::<DecoratorFunctionCall>() {
-                if decorator_function_call.is_some() {
-                    return Err(input.error("exactly one decorator function call is allowed"));
+                layer.override_return_type = Some(input.parse()?);
+            } else if option == "switch" {
+                if layer.switch.is_some() {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "at most one `switch` is allowed per decorator",
+                    ));
                 }
 
-                decorator_function_call = Some(parsed);
-                input.advance_to(&input_fork_1);
-            } else if read_exact_ident("debug", &&input_fork_2).is_ok() {
+                input.parse::<Ident>()?;
+                let name = if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    Some(input.parse()?)
+                } else {
+                    None
+                };
+                layer.switch = Some(LayerSwitch {
+                    span: option.span(),
+                    name,
+                });
+            } else if option == "debug" {
                 if debug {
-                    return Err(input.error("exactly one `debug` is allowed"));
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "exactly one `debug` is allowed",
+                    ));
                 }
 
+                input.parse::<Ident>()?;
                 debug = true;
+            } else if option == "assert_send" {
+                if assert_send.is_some() {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "exactly one `assert_send` is allowed",
+                    ));
+                }
 
-                input.advance_to(&input_fork_2);
-            } else if let Ok(parsed) = input_fork_3.parse::<OverrideReturnType>() {
-                if override_return_type.is_some() {
-                    return Err(input.error("at most one override_return_type list is allowed"));
+                input.parse::<Ident>()?;
+                assert_send = Some(option.span());
+            } else if option == "when" {
+                if when.is_some() {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "at most one `when` is allowed",
+                    ));
                 }
 
-                override_return_type = Some(parsed);
-                input.advance_to(&input_fork_3);
-            } else {
-                return Err(
-                    input.error("expected decorator function call or `hide_parameters` or `exact_parameters` or `override_return_type`")
-                );
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                read_exact_ident("cfg", &input)?;
+                let content;
+                parenthesized!(content in input);
+                when = Some(content.parse::<syn::Meta>()?);
+            } else if !parse_extra_arg(input)? {
+                let options = Self::OPTIONS.iter().chain(extra_options).copied();
+                return Err(unknown_name_error(
+                    &option,
+                    format!("unknown option `{option}`"),
+                    options,
+                ));
             }
+        }
 
-            first_item = false;
+        // options given before the first decorator function call may create a layer without one
+        layer_builders.retain(|layer| {
+            layer.decorator_function_call.is_some()
+                || layer.parameters_override.is_some()
+                || layer.pack_parameters.is_some()
+                || layer.with_context
+                || layer.reusable
+                || layer.with_arguments.is_some()
+                || layer.mode.is_some()
+                || layer.override_return_type.is_some()
+                || layer.switch.is_some()
+        });
+
+        // the condition belongs to every layer of the attribute, stacked attributes can have
+        // different ones
+        let mut layers = layer_builders
+            .into_iter()
+            .map(DecoratorLayerBuilder::build)
+            .collect::<syn::Result<Vec<_>>>()?;
+        for layer in layers.iter_mut() {
+            layer.when.clone_from(&when);
         }
 
         Ok(Self {
             debug,
-            decorator_function_call: decorator_function_call
-                .ok_or_else(|| input.error("exactly one decorator function call is allowed"))?,
-            parameters_override: parameter_override,
-            override_return_type,
+            assert_send,
+            layers,
         })
     }
 }
 
-enum DecoratedFnArgName {
-    Receiver,
-    Pat(Pat),
+fn current_layer_builder(
+    layer_builders: &mut Vec<DecoratorLayerBuilder>,
+    span: Span,
+) -> &mut DecoratorLayerBuilder {
+    if layer_builders.is_empty() {
+        layer_builders.push(DecoratorLayerBuilder::new(span));
+    }
+
+    layer_builders.last_mut().unwrap()
 }
 
-impl ToTokens for DecoratedFnArgName {
-    fn to_tokens(&self, tokens: &mut TokenStream2) {
-        match self {
-            DecoratedFnArgName::Receiver => tokens.extend(quote! { self }),
-            DecoratedFnArgName::Pat(p) => tokens.extend(quote! { #p }),
+/// Returns the candidate that is the most similar to `name`, if it is similar enough to be a
+/// misspelling of it.
+fn similar_name<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let edit_distance = |a: &str, b: &str| {
+        let b = b.chars().collect::<Vec<_>>();
+        let mut distances = (0..=b.len()).collect::<Vec<_>>();
+        for (i, a_char) in a.chars().enumerate() {
+            let mut previous_diagonal = distances[0];
+            distances[0] = i + 1;
+            for (j, b_char) in b.iter().enumerate() {
+                let substitution = previous_diagonal + usize::from(a_char != *b_char);
+                previous_diagonal = distances[j + 1];
+                distances[j + 1] = substitution.min(distances[j] + 1).min(distances[j + 1] + 1);
+            }
+        }
+        distances[b.len()]
+    };
+
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Creates an error about an unknown name, suggesting the most similar candidate.
+fn unknown_name_error<'a>(
+    name: &Ident,
+    message: String,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> syn::Error {
+    match similar_name(&name.to_string(), candidates) {
+        Some(candidate) => syn::Error::new(
+            name.span(),
+            format!("{message}, did you mean `{candidate}`?"),
+        ),
+        None => syn::Error::new(name.span(), message),
+    }
+}
+
+fn is_use_decorator_attr(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .map(|segment| segment.ident == "use_decorator" || segment.ident == "use_impl_decorator")
+        .unwrap_or(false)
+}
+
+fn is_skip_decorator_attr(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .map(|segment| segment.ident == "skip_decorator")
+        .unwrap_or(false)
+}
+
+struct NameList(Vec<Ident>);
+
+impl NameList {
+    fn parse_named(ident_name: &str, input: syn::parse::ParseStream) -> syn::Result<Self> {
+        read_exact_ident(ident_name, &input)?;
+        input.parse::<Token![=]>()?;
+        let content;
+        bracketed!(content in input);
+        let names = content
+            .parse_terminated(Ident::parse, Token![,])?
+            .into_iter()
+            .collect();
+
+        Ok(NameList(names))
+    }
+
+    fn contains(&self, ident: &Ident) -> bool {
+        self.0.iter().any(|name| name == ident)
+    }
+}
+
+struct DecorateImplArg {
+    use_decorator_arg: UseDecoratorArg,
+    include: Option<NameList>,
+    exclude: Option<NameList>,
+}
+
+impl DecorateImplArg {
+    /// Checks that the methods listed in `include` and `exclude` exist.
+    fn check_method_names(&self, method_names: &[String], item_kind: &str) -> syn::Result<()> {
+        for name in self
+            .include
+            .iter()
+            .chain(self.exclude.iter())
+            .flat_map(|list| list.0.iter())
+        {
+            if !method_names.iter().any(|method_name| name == method_name) {
+                return Err(unknown_name_error(
+                    name,
+                    format!("no method named `{name}` in this {item_kind}"),
+                    method_names.iter().map(String::as_str),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_included(&self, method_ident: &Ident) -> bool {
+        let included = self
+            .include
+            .as_ref()
+            .map(|include| include.contains(method_ident))
+            .unwrap_or(true);
+        let excluded = self
+            .exclude
+            .as_ref()
+            .map(|exclude| exclude.contains(method_ident))
+            .unwrap_or(false);
+
+        included && !excluded
+    }
+}
+
+impl Parse for DecorateImplArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut include = None;
+        let mut exclude = None;
+
+        let use_decorator_arg =
+            UseDecoratorArg::parse_with(input, &["include", "exclude"], |input| {
+                let option = input.fork().parse::<Ident>()?;
+                if option == "include" {
+                    if include.is_some() {
+                        return Err(syn::Error::new(
+                            option.span(),
+                            "at most one `include` list is allowed",
+                        ));
+                    }
+
+                    include = Some(NameList::parse_named("include", input)?);
+                } else if option == "exclude" {
+                    if exclude.is_some() {
+                        return Err(syn::Error::new(
+                            option.span(),
+                            "at most one `exclude` list is allowed",
+                        ));
+                    }
+
+                    exclude = Some(NameList::parse_named("exclude", input)?);
+                } else {
+                    return Ok(false);
+                }
+
+                Ok(true)
+            })?;
+
+        Ok(Self {
+            use_decorator_arg,
+            include,
+            exclude,
+        })
+    }
+}
+
+/// A list of function names, `*` matches any part of a name, e.g. `helper_*`.
+struct NamePatternList(Vec<NamePattern>);
+
+struct NamePattern {
+    pattern: String,
+    span: Span,
+}
+
+impl NamePatternList {
+    fn parse_named(ident_name: &str, input: syn::parse::ParseStream) -> syn::Result<Self> {
+        read_exact_ident(ident_name, &input)?;
+        input.parse::<Token![=]>()?;
+        let content;
+        bracketed!(content in input);
+        let patterns = content
+            .parse_terminated(NamePattern::parse, Token![,])?
+            .into_iter()
+            .collect();
+
+        Ok(NamePatternList(patterns))
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        self.0.iter().any(|pattern| pattern.matches(name))
+    }
+}
+
+impl Parse for NamePattern {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let mut pattern = String::new();
+        while !input.is_empty() && !input.peek(Token![,]) {
+            if input.peek(Token![*]) {
+                input.parse::<Token![*]>()?;
+                pattern.push('*');
+            } else {
+                pattern.push_str(&input.call(Ident::parse_any)?.unraw().to_string());
+            }
         }
+        if pattern.is_empty() {
+            return Err(input.error("expected a function name"));
+        }
+
+        Ok(NamePattern { pattern, span })
+    }
+}
+
+impl NamePattern {
+    fn matches(&self, name: &str) -> bool {
+        let mut parts = self.pattern.split('*');
+        let first = parts.next().unwrap_or_default();
+        let Some(mut rest) = name.strip_prefix(first) else {
+            return false;
+        };
+        let parts = parts.collect::<Vec<_>>();
+        let Some((last, middle)) = parts.split_last() else {
+            return rest.is_empty();
+        };
+        for part in middle {
+            match rest.find(part) {
+                Some(index) => rest = &rest[index + part.len()..],
+                None => return false,
+            }
+        }
+        rest.ends_with(last)
+    }
+}
+
+struct DecorateModuleArg {
+    use_decorator_arg: UseDecoratorArg,
+    /// Whether only the functions with a `pub` visibility are decorated.
+    only_pub: bool,
+    include: Option<NamePatternList>,
+    exclude: Option<NamePatternList>,
+    /// Whether the methods of the impl blocks and the functions of the nested modules are
+    /// decorated too.
+    recursive: bool,
+}
+
+impl Parse for DecorateModuleArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut only_pub = None;
+        let mut include = None;
+        let mut exclude = None;
+        let mut recursive = None;
+
+        let use_decorator_arg = UseDecoratorArg::parse_with(
+            input,
+            &["only", "include", "exclude", "recursive"],
+            |input| {
+                let option = input.fork().parse::<Ident>()?;
+                let is_given = match option.to_string().as_str() {
+                    "only" => only_pub.is_some(),
+                    "include" => include.is_some(),
+                    "exclude" => exclude.is_some(),
+                    "recursive" => recursive.is_some(),
+                    _ => return Ok(false),
+                };
+                if is_given {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        format!("at most one `{option}` is allowed"),
+                    ));
+                }
+
+                if option == "only" {
+                    input.parse::<Ident>()?;
+                    input.parse::<Token![=]>()?;
+                    input.parse::<Token![pub]>()?;
+                    only_pub = Some(true);
+                } else if option == "include" {
+                    include = Some(NamePatternList::parse_named("include", input)?);
+                } else if option == "exclude" {
+                    exclude = Some(NamePatternList::parse_named("exclude", input)?);
+                } else {
+                    input.parse::<Ident>()?;
+                    recursive = Some(true);
+                }
+
+                Ok(true)
+            },
+        )?;
+
+        Ok(Self {
+            use_decorator_arg,
+            only_pub: only_pub.unwrap_or(false),
+            include,
+            exclude,
+            recursive: recursive.unwrap_or(false),
+        })
     }
 }
 
+/// A parameter of a wrapper function. The wrapper function binds every parameter to a plain
+/// identifier, so parameters with any pattern can be forwarded, and the original function
+/// destructures them.
+struct WrapperFnParam {
+    /// The name `hide_parameters` and `exact_parameters` refer to the parameter with. The receiver
+    /// is called `self`, and parameters that are not plain identifiers have no name.
+    name: Option<String>,
+    binding: Ident,
+    /// Whether the parameter is a mutable reference, which is reborrowed instead of cloned by
+    /// reusable closures.
+    is_mut_ref: bool,
+}
+
+/// Replaces the patterns of the parameters with their bindings in the signature of a wrapper
+/// function.
+fn bind_wrapper_fn_params(wrapper_fn_sig: &mut Signature) -> Vec<WrapperFnParam> {
+    wrapper_fn_sig
+        .inputs
+        .iter_mut()
+        .enumerate()
+        .map(|(index, param)| match param {
+            FnArg::Receiver(receiver) => WrapperFnParam {
+                name: Some("self".into()),
+                binding: Ident::new("_self", Span::mixed_site()),
+                is_mut_ref: receiver.reference.is_some() && receiver.mutability.is_some(),
+            },
+            FnArg::Typed(param) => {
+                let is_mut_ref = matches!(
+                    param.ty.as_ref(),
+                    syn::Type::Reference(reference) if reference.mutability.is_some()
+                );
+                let wrapper_fn_param = match param.pat.as_ref() {
+                    Pat::Ident(pat_ident)
+                        if pat_ident.by_ref.is_none() && pat_ident.subpat.is_none() =>
+                    {
+                        WrapperFnParam {
+                            name: Some(pat_ident.ident.to_string()),
+                            binding: pat_ident.ident.clone(),
+                            is_mut_ref,
+                        }
+                    }
+                    _ => WrapperFnParam {
+                        name: None,
+                        binding: Ident::new(&format!("arg_{index}"), Span::mixed_site()),
+                        is_mut_ref,
+                    },
+                };
+
+                let binding = &wrapper_fn_param.binding;
+                *param.pat = syn::parse_quote! { #binding };
+
+                wrapper_fn_param
+            }
+        })
+        .collect()
+}
+
 fn use_decorator_impl(
     arg: TokenStream,
     input: TokenStream,
@@ -227,135 +899,1794 @@ fn use_decorator_impl(
 ) -> TokenStream {
     let use_decorator_arg: UseDecoratorArg = syn::parse_macro_input!(arg);
 
-    let decorator_fn_path = &use_decorator_arg.decorator_function_call.middleware_fn_path;
-    let decorator_fn_params = &use_decorator_arg.decorator_function_call.middleware_params;
+    let item_impl: ImplItemFn = match syn::parse(input.clone()) {
+        Ok(item_impl) => item_impl,
+        Err(e) => {
+            // method declarations of traits do not have a body
+            return match syn::parse::<TraitItemFn>(input.clone()) {
+                Ok(trait_item_fn) if is_impl_decorator && trait_item_fn.default.is_none() => {
+                    match decorate_required_trait_fn(
+                        use_decorator_arg,
+                        trait_item_fn,
+                        &inner_fn_vis(true),
+                    ) {
+                        Ok(decorated_fn) => decorated_fn.into_token_stream().into(),
+                        Err(e) => e.to_compile_error().into(),
+                    }
+                }
+                // the functions of `extern` blocks do not have a body either
+                _ => match syn::parse::<ForeignItemFn>(input) {
+                    Ok(foreign_item_fn) => syn::Error::new(
+                        foreign_item_fn.sig.fn_token.span,
+                        "functions declared in `extern` blocks cannot be decorated, decorate a function calling them instead",
+                    )
+                    .to_compile_error()
+                    .into(),
+                    Err(_) => e.to_compile_error().into(),
+                },
+            };
+        }
+    };
 
-    let mut item_impl: ImplItemFn = syn::parse_macro_input!(input);
-    let ImplItemFn {
-        attrs: _decorated_fn_attrs,
-        vis: decorated_fn_vis,
-        defaultness: _decorated_fn_defaultness,
-        sig: decorated_fn_sig,
-        block: _decorated_fn_block,
-    } = item_impl.clone();
+    let inner_fn_name_prefix = item_impl.sig.ident.to_string();
+    match decorate_fn(
+        use_decorator_arg,
+        item_impl,
+        is_impl_decorator,
+        &inner_fn_vis(is_impl_decorator),
+        &inner_fn_name_prefix,
+    ) {
+        Ok(decorated_fn) => decorated_fn.into_token_stream().into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
 
-    let wrapper_fn_signature_output =
-        if let Some(override_return_type) = use_decorator_arg.override_return_type {
-            quote! {
-                -> #override_return_type
-            }
+/// The expansion of a decorated function.
+struct DecoratedFn {
+    /// The outermost layer, it has the name and the visibility of the decorated function.
+    wrapper_fn: TokenStream2,
+    /// The original function and the inner layers.
+    inner_fns: TokenStream2,
+}
+
+impl ToTokens for DecoratedFn {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.inner_fns.to_tokens(tokens);
+        self.wrapper_fn.to_tokens(tokens);
+    }
+}
+
+/// Moves the layers of the further `use_decorator` attributes into `use_decorator_arg`, as inner
+/// layers.
+fn take_use_decorator_attrs(
+    use_decorator_arg: &mut UseDecoratorArg,
+    attrs: &mut Vec<Attribute>,
+) -> syn::Result<()> {
+    let mut decorated_fn_attrs = Vec::new();
+    for attr in std::mem::take(attrs) {
+        if is_use_decorator_attr(&attr) {
+            let inner_arg: UseDecoratorArg = attr.parse_args()?;
+            use_decorator_arg.debug |= inner_arg.debug;
+            use_decorator_arg.assert_send = use_decorator_arg.assert_send.or(inner_arg.assert_send);
+            use_decorator_arg.layers.extend(inner_arg.layers);
         } else {
-            let output = decorated_fn_sig.output.clone();
-            quote! {
-                #output
-            }
+            decorated_fn_attrs.push(attr);
+        }
+    }
+    *attrs = decorated_fn_attrs;
+
+    Ok(())
+}
+
+/// The attributes of a decorated function, which are moved to or copied onto the generated
+/// functions. The other attributes are related to the body, so they stay on the original function.
+struct DecoratedFnAttrs {
+    /// Attributes describing the interface of the function, they are moved to the wrapper.
+    wrapper: Vec<Attribute>,
+    /// Conditional compilation and lint attributes, they are copied onto every generated function.
+    shared: Vec<Attribute>,
+}
+
+impl DecoratedFnAttrs {
+    /// The symbol attributes are moved too, so the exported symbol is the wrapper.
+    const WRAPPER: [&'static str; 9] = [
+        "doc",
+        "must_use",
+        "deprecated",
+        "inline",
+        "cold",
+        "track_caller",
+        "no_mangle",
+        "export_name",
+        "link_section",
+    ];
+    const SHARED: [&'static str; 7] = [
+        "cfg", "cfg_attr", "allow", "warn", "deny", "forbid", "expect",
+    ];
+
+    /// Takes the wrapper attributes out of `attrs`, and copies the shared ones.
+    fn take(attrs: &mut Vec<Attribute>) -> Self {
+        // unsafe attributes like `#[unsafe(no_mangle)]` are matched by the attribute inside
+        let is_one_of = |attr: &Attribute, names: &[&str]| {
+            let path = match attr.path().is_ident("unsafe") {
+                true => match attr.parse_args::<syn::Meta>() {
+                    Ok(meta) => meta.path().clone(),
+                    Err(_) => return false,
+                },
+                false => attr.path().clone(),
+            };
+            names.iter().any(|name| path.is_ident(name))
         };
 
-    let mut wrapper_fn_signature_without_output = decorated_fn_sig.clone();
-    wrapper_fn_signature_without_output.output = syn::ReturnType::Default;
+        let (wrapper, original) = std::mem::take(attrs)
+            .into_iter()
+            .partition(|attr| is_one_of(attr, &Self::WRAPPER));
+        *attrs = original;
 
-    let new_fn_name = wrapper_fn_signature_without_output.ident.to_string();
-    let new_fn_ident = Ident::new(&(new_fn_name + "_fn_decorator_original"), Span::call_site());
-    item_impl.sig.ident = new_fn_ident.clone();
+        Self {
+            wrapper,
+            shared: attrs
+                .iter()
+                .filter(|attr| is_one_of(attr, &Self::SHARED))
+                .cloned()
+                .collect(),
+        }
+    }
+}
 
-    let fn_param_names: Punctuated<DecoratedFnArgName, Token![,]> =
-        wrapper_fn_signature_without_output
-            .inputs
-            .iter()
-            .map(|param| match param {
-                FnArg::Receiver(_) => DecoratedFnArgName::Receiver,
-                FnArg::Typed(p) => DecoratedFnArgName::Pat(*p.pat.clone()),
-            })
-            .collect();
+/// The interface of a decorated function, the outermost layer is generated with it.
+struct DecoratedFnInterface {
+    attrs: DecoratedFnAttrs,
+    vis: Visibility,
+    sig: Signature,
+    /// The visibility of the original function and of the inner layers.
+    inner_fn_vis: Visibility,
+}
 
-    let decorator_fn_params = if decorator_fn_params.is_empty() {
-        quote! {}
-    } else {
-        quote! {#decorator_fn_params,}
-    };
+/// Expands `item_impl` with the given decorator layers, and with the layers of the further
+/// `use_decorator` attributes found on it. The names of the inner functions start with
+/// `inner_fn_name_prefix`, and they have the `inner_fn_vis` visibility.
+fn decorate_fn(
+    mut use_decorator_arg: UseDecoratorArg,
+    mut item_impl: ImplItemFn,
+    is_impl_decorator: bool,
+    inner_fn_vis: &Visibility,
+    inner_fn_name_prefix: &str,
+) -> syn::Result<DecoratedFn> {
+    take_use_decorator_attrs(&mut use_decorator_arg, &mut item_impl.attrs)?;
 
-    let new_fn_pointer = if is_impl_decorator {
-        quote! {Self::#new_fn_ident}
-    } else {
-        quote! {#new_fn_ident}
-    };
+    if use_decorator_arg
+        .layers
+        .iter()
+        .any(|layer| layer.when.is_some())
+    {
+        return decorate_fn_conditionally(
+            use_decorator_arg,
+            item_impl,
+            is_impl_decorator,
+            inner_fn_vis,
+            inner_fn_name_prefix,
+        );
+    }
 
-    let (closure_async, decorator_await) = if item_impl.sig.asyncness.is_some() {
-        (quote! { async }, quote! { .await })
-    } else {
-        (quote! {}, quote! {})
+    if use_decorator_arg.layers.is_empty() {
+        return Ok(DecoratedFn {
+            wrapper_fn: quote! { #item_impl },
+            inner_fns: quote! {},
+        });
+    }
+
+    let interface = DecoratedFnInterface {
+        attrs: DecoratedFnAttrs::take(&mut item_impl.attrs),
+        vis: item_impl.vis.clone(),
+        sig: item_impl.sig.clone(),
+        inner_fn_vis: inner_fn_vis.clone(),
     };
+    let original_fn_ident = Ident::new(
+        &format!("{inner_fn_name_prefix}_fn_decorator_original"),
+        inner_fn_span(is_impl_decorator),
+    );
+    item_impl.sig.ident = original_fn_ident.clone();
+    item_impl.vis = inner_fn_vis.clone();
+    // only the wrapper is called through the ABI of the decorated function, decorator functions
+    // receive Rust functions
+    item_impl.sig.abi = None;
+    item_impl.attrs.push(syn::parse_quote! { #[doc(hidden)] });
+
+    let decorated_fn = expand_decorator_layers(
+        &use_decorator_arg,
+        quote! { #item_impl },
+        &interface,
+        &original_fn_ident,
+        is_impl_decorator,
+        inner_fn_name_prefix,
+    )?;
+
+    if use_decorator_arg.debug || std::env::var_os("FN_DECORATOR_DEBUG").is_some() {
+        write_debug_expansion(&interface.sig.ident, &decorated_fn.to_token_stream())?;
+    }
+
+    Ok(decorated_fn)
+}
 
-    let tokens = if let Some(parameters_override) = use_decorator_arg.parameters_override {
-        let fn_param_names: Punctuated<Ident, Token![,]> = fn_param_names
+/// Expands a function decorated with conditional layers to a variant for every combination of the
+/// conditions. The variants are gated with `cfg`, which is copied onto every generated function,
+/// so the disabled layers do not exist in the build at all.
+fn decorate_fn_conditionally(
+    use_decorator_arg: UseDecoratorArg,
+    item_impl: ImplItemFn,
+    is_impl_decorator: bool,
+    inner_fn_vis: &Visibility,
+    inner_fn_name_prefix: &str,
+) -> syn::Result<DecoratedFn> {
+    // the conditions are compared by their tokens
+    let condition_key = |condition: &syn::Meta| condition.to_token_stream().to_string();
+    let mut conditions: Vec<&syn::Meta> = Vec::new();
+    for condition in use_decorator_arg
+        .layers
+        .iter()
+        .filter_map(|layer| layer.when.as_ref())
+    {
+        if !conditions
             .iter()
-            .map(|param_name| match param_name {
-                DecoratedFnArgName::Receiver => Ident::new("_self", Span::call_site()),
-                DecoratedFnArgName::Pat(pat) => {
-                    Ident::new(&pat.to_token_stream().to_string(), Span::call_site())
-                }
-            })
-            .collect();
+            .any(|c| condition_key(c) == condition_key(condition))
+        {
+            conditions.push(condition);
+        }
+    }
 
-        let closure_params = match parameters_override {
-            ParametersOverride::Hide(hide_parameters_list) => fn_param_names
-                .iter()
-                .filter(|param_name| {
-                    let ident_str = param_name.to_string();
-                    if ident_str == "_self" {
-                        !hide_parameters_list.0.contains(&"self".to_string())
-                    } else {
-                        !hide_parameters_list.0.contains(&ident_str)
-                    }
-                })
-                .collect::<Punctuated<&Ident, Token![,]>>(),
-            ParametersOverride::Exact(exact_parameters_list) => fn_param_names
+    // the variant with every condition holding is the last one, so its expansion is the one left
+    // by `debug`
+    let mut decorated_fn = DecoratedFn {
+        wrapper_fn: quote! {},
+        inner_fns: quote! {},
+    };
+    for variant in 0..1usize << conditions.len() {
+        let holds = |condition: &syn::Meta| {
+            conditions
                 .iter()
-                .filter(|param_name| {
-                    let ident_str = param_name.to_string();
-                    if ident_str == "_self" {
-                        exact_parameters_list.0.contains(&"self".to_string())
-                    } else {
-                        exact_parameters_list.0.contains(&ident_str)
-                    }
-                })
-                .collect::<Punctuated<&Ident, Token![,]>>(),
+                .position(|c| condition_key(c) == condition_key(condition))
+                .is_some_and(|index| variant & (1 << index) != 0)
         };
+        let predicates = conditions.iter().map(|condition| {
+            if holds(condition) {
+                quote! {#condition}
+            } else {
+                quote! {not(#condition)}
+            }
+        });
 
-        let self_redeclaration = if is_impl_decorator {
-            quote! {let _self = self;}
-        } else {
-            quote! {}
-        };
+        let mut variant_arg = use_decorator_arg.clone();
+        variant_arg
+            .layers
+            .retain(|layer| layer.when.as_ref().map(holds).unwrap_or(true));
+        for layer in variant_arg.layers.iter_mut() {
+            layer.when = None;
+        }
+        let mut variant_fn = item_impl.clone();
+        variant_fn
+            .attrs
+            .push(syn::parse_quote! { #[cfg(all(#(#predicates),*))] });
 
-        quote! {
-            #item_impl
+        let variant = decorate_fn(
+            variant_arg,
+            variant_fn,
+            is_impl_decorator,
+            inner_fn_vis,
+            inner_fn_name_prefix,
+        )?;
+        decorated_fn.wrapper_fn.extend(variant.wrapper_fn);
+        decorated_fn.inner_fns.extend(variant.inner_fns);
+    }
 
-            #decorated_fn_vis #wrapper_fn_signature_without_output #wrapper_fn_signature_output {
-                #self_redeclaration
+    Ok(decorated_fn)
+}
 
-                #decorator_fn_path(
-                    #decorator_fn_params
-                    move |#closure_params| #closure_async { #new_fn_pointer(#fn_param_names)#decorator_await },
-                    #closure_params)
-                #decorator_await
-            }
-        }
-    } else {
-        quote! {
-            #item_impl
+/// Expands a method declaration of a trait. The declaration is renamed to `<name>_impl`, so the
+/// implementors provide that one, and the decorated method becomes a provided method calling it.
+/// The declaration stays in the trait, so it is expanded together with the wrapper.
+fn decorate_required_trait_fn(
+    mut use_decorator_arg: UseDecoratorArg,
+    mut trait_item_fn: TraitItemFn,
+    inner_fn_vis: &Visibility,
+) -> syn::Result<DecoratedFn> {
+    take_use_decorator_attrs(&mut use_decorator_arg, &mut trait_item_fn.attrs)?;
+    // the implementors would have to implement a different method depending on the condition
+    if let Some(layer) = use_decorator_arg
+        .layers
+        .iter()
+        .find(|layer| layer.when.is_some())
+    {
+        return Err(syn::Error::new_spanned(
+            &layer.decorator_function_call.middleware_fn_path,
+            "`when` is not supported on method declarations of traits",
+        ));
+    }
 
-            #decorated_fn_vis #wrapper_fn_signature_without_output #wrapper_fn_signature_output {
-                #decorator_fn_path(#decorator_fn_params #new_fn_pointer, #fn_param_names)#decorator_await
-            }
-        }
+    let interface = DecoratedFnInterface {
+        attrs: DecoratedFnAttrs::take(&mut trait_item_fn.attrs),
+        vis: Visibility::Inherited,
+        sig: trait_item_fn.sig.clone(),
+        inner_fn_vis: inner_fn_vis.clone(),
     };
+    let inner_fn_name_prefix = interface.sig.ident.to_string();
+    let required_fn_ident = Ident::new(
+        &format!("{inner_fn_name_prefix}_impl"),
+        interface.sig.ident.span(),
+    );
+    trait_item_fn.sig.ident = required_fn_ident.clone();
+    trait_item_fn.sig.abi = None;
 
-    if use_decorator_arg.debug {
-        panic!("Generated code = `{}`", tokens);
+    let mut decorated_fn = expand_decorator_layers(
+        &use_decorator_arg,
+        quote! {},
+        &interface,
+        &required_fn_ident,
+        true,
+        &inner_fn_name_prefix,
+    )?;
+    let wrapper_fn = decorated_fn.wrapper_fn;
+    decorated_fn.wrapper_fn = quote! {
+        #trait_item_fn
+        #wrapper_fn
+    };
+
+    if use_decorator_arg.debug || std::env::var_os("FN_DECORATOR_DEBUG").is_some() {
+        write_debug_expansion(&interface.sig.ident, &decorated_fn.to_token_stream())?;
+    }
+
+    Ok(decorated_fn)
+}
+
+/// Generates a function for every decorator layer. The innermost layer calls the original
+/// function, the others call the next inner layer.
+fn expand_decorator_layers(
+    use_decorator_arg: &UseDecoratorArg,
+    original_fn: TokenStream2,
+    interface: &DecoratedFnInterface,
+    original_fn_ident: &Ident,
+    is_impl_decorator: bool,
+    inner_fn_name_prefix: &str,
+) -> syn::Result<DecoratedFn> {
+    if let Some(constness) = &interface.sig.constness {
+        return Err(syn::Error::new_spanned(
+            constness,
+            "const functions cannot be decorated, because decorator functions cannot be called in const contexts",
+        ));
+    }
+
+    let layer_count = use_decorator_arg.layers.len();
+    let layer_fn_ident = |layer_index: usize| {
+        if layer_index == 0 {
+            interface.sig.ident.clone()
+        } else if layer_index == layer_count {
+            original_fn_ident.clone()
+        } else {
+            Ident::new(
+                &format!("{inner_fn_name_prefix}_fn_decorator_layer_{layer_index}"),
+                inner_fn_span(is_impl_decorator),
+            )
+        }
+    };
+
+    let mut decorated_fn = DecoratedFn {
+        wrapper_fn: quote! {},
+        inner_fns: original_fn,
+    };
+
+    let decorated_fn_name = interface.sig.ident.unraw().to_string();
+    let is_async = interface.sig.asyncness.is_some();
+    let is_method = interface.sig.receiver().is_some();
+    let decorated_fn_context = quote_spanned! {interface.sig.ident.span()=>
+        {
+            const FN_CONTEXT: ::fn_decorator_std::FnContext = ::fn_decorator_std::FnContext {
+                name: #decorated_fn_name,
+                module_path: ::core::module_path!(),
+                file: ::core::file!(),
+                line: ::core::line!(),
+                is_async: #is_async,
+                is_method: #is_method,
+            };
+            &FN_CONTEXT
+        }
+    };
+
+    // layers are generated from the innermost one, so each of them knows the return type of the
+    // function it wraps
+    let mut wrapped_fn_output = interface.sig.output.clone();
+    let mut wrapped_fn_is_async = interface.sig.asyncness.is_some();
+    for (layer_index, layer) in use_decorator_arg.layers.iter().enumerate().rev() {
+        let default_mode = if wrapped_fn_is_async {
+            DecoratorMode::Async
+        } else {
+            DecoratorMode::Sync
+        };
+        let mode = layer.mode.unwrap_or(default_mode);
+        // a disabled layer returns the result of the wrapped function as it is
+        if let Some(switch) = &layer.switch {
+            if layer.override_return_type.is_some() || mode != default_mode {
+                return Err(syn::Error::new(
+                    switch.span,
+                    "`switch` cannot be given together with `override_return_type` or `mode`",
+                ));
+            }
+            // the default name would not contain the type, so the same-named methods of different
+            // types would share the switch
+            if is_impl_decorator && switch.name.is_none() {
+                return Err(syn::Error::new(
+                    switch.span,
+                    "the `switch` of a method has to be named, e.g. `switch = \"Type::method\"`",
+                ));
+            }
+        }
+
+        let mut wrapper_fn_sig = interface.sig.clone();
+        wrapper_fn_sig.ident = layer_fn_ident(layer_index);
+        if layer_index != 0 {
+            wrapper_fn_sig.abi = None;
+        }
+        wrapper_fn_sig.asyncness = match mode {
+            DecoratorMode::Async => Some(Default::default()),
+            DecoratorMode::Sync | DecoratorMode::Future => None,
+        };
+        if let Some(override_return_type) = &layer.override_return_type {
+            wrapper_fn_sig.output = syn::parse_quote! { -> #override_return_type };
+        } else if mode == DecoratorMode::Future && wrapped_fn_is_async {
+            let captured_lifetimes =
+                name_elided_lifetimes(&mut wrapper_fn_sig, &mut wrapped_fn_output);
+            let wrapped_fn_output = match wrapped_fn_output {
+                syn::ReturnType::Default => quote! { () },
+                syn::ReturnType::Type(_, output) => quote! { #output },
+            };
+            // the lifetimes appearing in the bounds are captured by the future, so they are named
+            // in an output type resolving to the output of the wrapped function
+            let wrapped_fn_output = if captured_lifetimes.is_empty() {
+                wrapped_fn_output
+            } else {
+                let captured_lifetimes = quote! { (#(&#captured_lifetimes (),)*) };
+                quote! {
+                    <::core::iter::Map<
+                        ::core::iter::Empty<#captured_lifetimes>,
+                        fn(#captured_lifetimes) -> #wrapped_fn_output,
+                    > as ::core::iter::Iterator>::Item
+                }
+            };
+            wrapper_fn_sig.output = syn::parse_quote! {
+                -> impl ::core::future::Future<Output = #wrapped_fn_output>
+            };
+        } else {
+            wrapper_fn_sig.output = wrapped_fn_output;
+        }
+        wrapped_fn_output = wrapper_fn_sig.output.clone();
+
+        let shared_attrs = &interface.attrs.shared;
+        let wrapper_fn = if layer_index == 0 {
+            let wrapper_attrs = &interface.attrs.wrapper;
+            WrapperFn {
+                attrs: quote! { #(#wrapper_attrs)* #(#shared_attrs)* },
+                vis: interface.vis.clone(),
+                sig: wrapper_fn_sig,
+                // the expansion depends on these environment variables, reading them in the
+                // generated code makes cargo rebuild the crate when they change
+                prelude: quote! {
+                    const _: ::core::option::Option<&str> =
+                        ::core::option_env!("FN_DECORATOR_DEBUG");
+                    const _: ::core::option::Option<&str> =
+                        ::core::option_env!("FN_DECORATOR_DEBUG_DIR");
+                },
+            }
+        } else {
+            WrapperFn {
+                attrs: quote! { #[doc(hidden)] #(#shared_attrs)* },
+                vis: interface.inner_fn_vis.clone(),
+                sig: wrapper_fn_sig,
+                prelude: quote! {},
+            }
+        };
+
+        // the inner functions of free functions are nested into the wrapper, so they cannot be
+        // called directly
+        let nested_fns = if layer_index == 0 && !is_impl_decorator {
+            std::mem::take(&mut decorated_fn.inner_fns)
+        } else {
+            quote! {}
+        };
+
+        let layer_fn = expand_decorator_layer(
+            layer,
+            wrapper_fn,
+            nested_fns,
+            WrappedFn {
+                ident: layer_fn_ident(layer_index + 1),
+                is_async: wrapped_fn_is_async,
+            },
+            &decorated_fn_context,
+            use_decorator_arg.assert_send.filter(|_| layer_index == 0),
+            is_impl_decorator,
+        )?;
+        wrapped_fn_is_async = mode == DecoratorMode::Async;
+        if layer_index == 0 {
+            decorated_fn.wrapper_fn = layer_fn;
+        } else {
+            decorated_fn.inner_fns.extend(layer_fn);
+        }
+    }
+
+    Ok(decorated_fn)
+}
+
+/// Names the elided lifetimes of the parameters and of `output` following the lifetime elision
+/// rules, and returns every lifetime parameter of the function. The future returned by a wrapper
+/// can capture the parameters only through named lifetimes, an async function captures them
+/// implicitly.
+fn name_elided_lifetimes(sig: &mut Signature, output: &mut syn::ReturnType) -> Vec<syn::Lifetime> {
+    struct ElidedLifetimeNamer {
+        named_lifetimes: Vec<syn::Lifetime>,
+        output_lifetime: Option<syn::Lifetime>,
+    }
+
+    impl ElidedLifetimeNamer {
+        fn next_lifetime(&mut self) -> syn::Lifetime {
+            if let Some(output_lifetime) = &self.output_lifetime {
+                return output_lifetime.clone();
+            }
+
+            let lifetime = syn::Lifetime::new(
+                &format!("'__fn_decorator_{}", self.named_lifetimes.len()),
+                Span::mixed_site(),
+            );
+            self.named_lifetimes.push(lifetime.clone());
+            lifetime
+        }
+    }
+
+    impl VisitMut for ElidedLifetimeNamer {
+        fn visit_receiver_mut(&mut self, receiver: &mut syn::Receiver) {
+            if receiver.colon_token.is_some() {
+                return self.visit_type_mut(&mut receiver.ty);
+            }
+
+            if let Some((_, lifetime)) = &mut receiver.reference {
+                if lifetime
+                    .as_ref()
+                    .map(|lifetime| lifetime.ident == "_")
+                    .unwrap_or(true)
+                {
+                    let named_lifetime = self.next_lifetime();
+                    if let syn::Type::Reference(reference) = receiver.ty.as_mut() {
+                        reference.lifetime = Some(named_lifetime.clone());
+                    }
+                    *lifetime = Some(named_lifetime);
+                }
+            }
+        }
+
+        fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+            if reference.lifetime.is_none() {
+                reference.lifetime = Some(self.next_lifetime());
+            }
+            visit_mut::visit_type_reference_mut(self, reference);
+        }
+
+        fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+            if lifetime.ident == "_" {
+                *lifetime = self.next_lifetime();
+            }
+        }
+
+        // function pointers and `Fn` traits have their own lifetime elision
+        fn visit_type_bare_fn_mut(&mut self, _: &mut syn::TypeBareFn) {}
+
+        fn visit_parenthesized_generic_arguments_mut(
+            &mut self,
+            _: &mut syn::ParenthesizedGenericArguments,
+        ) {
+        }
+    }
+
+    let mut namer = ElidedLifetimeNamer {
+        named_lifetimes: Vec::new(),
+        output_lifetime: None,
+    };
+    for param in sig.inputs.iter_mut() {
+        namer.visit_fn_arg_mut(param);
+    }
+
+    for lifetime in namer.named_lifetimes.iter().rev() {
+        sig.generics.params.insert(
+            0,
+            GenericParam::Lifetime(syn::LifetimeParam::new(lifetime.clone())),
+        );
+    }
+
+    let lifetimes = sig
+        .generics
+        .lifetimes()
+        .map(|param| param.lifetime.clone())
+        .collect::<Vec<_>>();
+
+    // the output borrows from a reference receiver, or from the only lifetime of the function
+    namer.output_lifetime = match sig.receiver().and_then(|receiver| receiver.lifetime()) {
+        Some(receiver_lifetime) => Some(receiver_lifetime.clone()),
+        None if lifetimes.len() == 1 => Some(lifetimes[0].clone()),
+        None => None,
+    };
+    if namer.output_lifetime.is_some() {
+        namer.visit_return_type_mut(output);
+    }
+
+    lifetimes
+}
+
+/// The span of the names of the inner functions. The inner functions of free functions are nested
+/// into the wrapper, so their names do not collide with the names used in the wrapper. The inner
+/// functions of methods are associated functions, they are resolved at the call site anyways.
+fn inner_fn_span(is_impl_decorator: bool) -> Span {
+    if is_impl_decorator {
+        Span::call_site()
+    } else {
+        Span::mixed_site()
+    }
+}
+
+/// The visibility of the inner functions. The inner functions of methods are `pub(self)`, so they
+/// cannot be called from other modules, and they cannot be added to a trait definition, where
+/// visibilities are not allowed. The inner functions of default trait methods are moved out of the
+/// trait by `decorate_impl`.
+fn inner_fn_vis(is_impl_decorator: bool) -> Visibility {
+    if is_impl_decorator {
+        syn::parse_quote! { pub(self) }
+    } else {
+        Visibility::Inherited
+    }
+}
+
+/// Writes the pretty-printed expansion of a decorated function to
+/// `<dir>/fn_decorator/<crate name>/<function name>_<line>.rs`, the line tells apart the
+/// same-named methods of different types. The directory is taken from the
+/// `FN_DECORATOR_DEBUG_DIR`, `OUT_DIR` or `CARGO_TARGET_DIR` environment variables, in this
+/// order, and it is the `target` directory of the crate if none of them are set.
+fn write_debug_expansion(decorated_fn_ident: &Ident, tokens: &TokenStream2) -> syn::Result<()> {
+    let env_path = |name| std::env::var_os(name).map(std::path::PathBuf::from);
+    let dir = env_path("FN_DECORATOR_DEBUG_DIR")
+        .or_else(|| env_path("OUT_DIR"))
+        .or_else(|| env_path("CARGO_TARGET_DIR"))
+        .or_else(|| env_path("CARGO_MANIFEST_DIR").map(|dir| dir.join("target")))
+        .unwrap_or_else(|| "target".into())
+        .join("fn_decorator")
+        .join(std::env::var("CARGO_CRATE_NAME").unwrap_or_default());
+
+    // method declarations of traits are not valid items, they are written as they are
+    let expansion = match syn::parse2::<syn::File>(tokens.clone()) {
+        Ok(file) => prettyplease::unparse(&file),
+        Err(_) => tokens.to_string(),
+    };
+
+    let line = decorated_fn_ident.span().unwrap().line();
+    std::fs::create_dir_all(&dir)
+        .and_then(|()| {
+            std::fs::write(
+                dir.join(format!("{decorated_fn_ident}_{line}.rs")),
+                expansion,
+            )
+        })
+        .map_err(|e| {
+            syn::Error::new(
+                decorated_fn_ident.span(),
+                format!("could not write the expansion to {}: {e}", dir.display()),
+            )
+        })
+}
+
+/// A function generated for a decorator layer.
+struct WrapperFn {
+    attrs: TokenStream2,
+    vis: Visibility,
+    sig: Signature,
+    /// Items placed at the beginning of the body.
+    prelude: TokenStream2,
+}
+
+/// The function called by a decorator layer.
+struct WrappedFn {
+    ident: Ident,
+    is_async: bool,
+}
+
+fn expand_decorator_layer(
+    layer: &DecoratorLayer,
+    wrapper_fn: WrapperFn,
+    nested_fns: TokenStream2,
+    wrapped_fn: WrappedFn,
+    decorated_fn_context: &TokenStream2,
+    assert_send: Option<Span>,
+    is_impl_decorator: bool,
+) -> syn::Result<TokenStream2> {
+    let decorator_fn_path = &layer.decorator_function_call.middleware_fn_path;
+    let decorator_fn_params = &layer.decorator_function_call.middleware_params;
+    let wrapped_fn_ident = &wrapped_fn.ident;
+
+    let WrapperFn {
+        attrs: wrapper_fn_attrs,
+        vis: wrapper_fn_vis,
+        sig: mut wrapper_fn_sig,
+        prelude: wrapper_fn_prelude,
+    } = wrapper_fn;
+    let wrapper_fn_params = bind_wrapper_fn_params(&mut wrapper_fn_sig);
+    if let Some(parameters_override) = &layer.parameters_override {
+        let param_names = wrapper_fn_params
+            .iter()
+            .filter_map(|param| param.name.as_deref());
+        for name in parameters_override.names() {
+            if !param_names.clone().any(|param_name| name == param_name) {
+                return Err(unknown_name_error(
+                    name,
+                    format!("no parameter named `{name}` in the decorated function"),
+                    param_names.clone(),
+                ));
+            }
+        }
+    }
+
+    let fn_param_names: Punctuated<&Ident, Token![,]> = wrapper_fn_params
+        .iter()
+        .map(|param| &param.binding)
+        .collect();
+
+    let self_redeclaration = match wrapper_fn_sig.inputs.first() {
+        Some(FnArg::Receiver(_)) => {
+            let self_binding = &wrapper_fn_params[0].binding;
+            quote! {let #self_binding = self;}
+        }
+        _ => quote! {},
+    };
+
+    let decorator_fn_params = if decorator_fn_params.is_empty() {
+        quote! {}
+    } else {
+        quote! {#decorator_fn_params,}
+    };
+    let decorator_fn_params = if layer.with_context {
+        quote! {#decorated_fn_context, #decorator_fn_params}
+    } else {
+        decorator_fn_params
+    };
+
+    // lifetimes are left to inference, they cannot be given explicitly when they are late bound
+    let generic_args = wrapper_fn_sig
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => Some(&param.ident),
+            GenericParam::Const(param) => Some(&param.ident),
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let turbofish = if generic_args.is_empty() {
+        quote! {}
+    } else {
+        quote! {::<#(#generic_args),*>}
+    };
+
+    let mut new_fn_pointer = if is_impl_decorator {
+        quote! {Self::#wrapped_fn_ident #turbofish}
+    } else {
+        quote! {#wrapped_fn_ident #turbofish}
+    };
+
+    // the decorator function receives an unsafe function, so calling it needs an unsafe block
+    // there, the options passing a closure would turn it into a safe callable
+    if wrapper_fn_sig.unsafety.is_some()
+        && (layer.parameters_override.is_some() || layer.pack_parameters || layer.with_arguments)
+    {
+        return Err(syn::Error::new_spanned(
+            decorator_fn_path,
+            "`hide_parameters`, `exact_parameters`, `pack_parameters` and `with_arguments` cannot \
+             be used with unsafe functions, the decorator function receives the unsafe function \
+             itself",
+        ));
+    }
+
+    // the nested functions are declared in their own block, otherwise they would shadow the
+    // parameters of the wrapper
+    let wrapped_fn_declaration = if nested_fns.is_empty() {
+        quote! {}
+    } else {
+        let wrapped_fn_binding = Ident::new("wrapped_fn", Span::mixed_site());
+        let wrapped_fn_declaration =
+            quote! {let #wrapped_fn_binding = { #nested_fns #new_fn_pointer };};
+        new_fn_pointer = quote! {#wrapped_fn_binding};
+        wrapped_fn_declaration
+    };
+
+    let decorator_await = if wrapper_fn_sig.asyncness.is_some() {
+        quote! { .await }
+    } else {
+        quote! {}
+    };
+
+    // the parameters passed to the decorator function, the others are captured by the closure
+    let is_passed = wrapper_fn_params
+        .iter()
+        .map(|param| match (&layer.parameters_override, &param.name) {
+            (None, _) | (Some(ParametersOverride::Hide(_)), None) => true,
+            (Some(ParametersOverride::Exact(_)), None) => false,
+            (Some(ParametersOverride::Hide(hide_parameters_list)), Some(name)) => {
+                !hide_parameters_list.0.iter().any(|hidden| hidden == name)
+            }
+            (Some(ParametersOverride::Exact(exact_parameters_list)), Some(name)) => {
+                exact_parameters_list.0.iter().any(|exact| exact == name)
+            }
+        })
+        .collect::<Vec<_>>();
+    let closure_params = wrapper_fn_params
+        .iter()
+        .zip(&is_passed)
+        .filter(|(_, is_passed)| **is_passed)
+        .map(|(param, _)| &param.binding)
+        .collect::<Punctuated<&Ident, Token![,]>>();
+
+    // reusable closures clone the captured parameters or reborrow the mutable references, so they
+    // can be called more than once
+    let captured_param = |param: &WrapperFnParam| {
+        let binding = &param.binding;
+        if !layer.reusable {
+            Ok(quote! {#binding})
+        } else if param.is_mut_ref && wrapped_fn.is_async {
+            // the returned future would borrow the reborrowed reference from the closure, which
+            // cannot lend its captures to the values it returns
+            Err(syn::Error::new_spanned(
+                decorator_fn_path,
+                "a `reusable` callable cannot capture the mutable references of async functions, \
+                 because the returned futures would borrow them from the callable",
+            ))
+        } else if param.is_mut_ref {
+            Ok(quote! {&mut *#binding})
+        } else {
+            Ok(quote! {::core::clone::Clone::clone(&#binding)})
+        }
+    };
+    let closure_wrapped_fn_args = wrapper_fn_params
+        .iter()
+        .zip(&is_passed)
+        .map(|(param, is_passed)| {
+            if *is_passed {
+                let binding = &param.binding;
+                Ok(quote! {#binding})
+            } else {
+                captured_param(param)
+            }
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let (argument_declarations, decorator_call) = if layer.with_arguments {
+        // parameters without a name are not listed in the arguments
+        let is_argument = wrapper_fn_params
+            .iter()
+            .map(|param| match (&param.name, &layer.parameters_override) {
+                (None, _) => false,
+                (Some(_), None) => true,
+                (Some(name), Some(ParametersOverride::Hide(hide_parameters_list))) => {
+                    !hide_parameters_list.0.iter().any(|hidden| hidden == name)
+                }
+                (Some(name), Some(ParametersOverride::Exact(exact_parameters_list))) => {
+                    exact_parameters_list.0.iter().any(|exact| exact == name)
+                }
+            })
+            .collect::<Vec<_>>();
+        let arguments = wrapper_fn_params
+            .iter()
+            .zip(&is_argument)
+            .filter(|(_, is_argument)| **is_argument)
+            .map(|(param, _)| param);
+        let argument_declarations = arguments.clone().map(|param| {
+            let binding = &param.binding;
+            quote! {let #binding = ::fn_decorator_std::Argument::new(#binding);}
+        });
+        let argument_names = arguments.clone().filter_map(|param| param.name.as_deref());
+        let argument_bindings = arguments.map(|param| &param.binding);
+        let wrapped_fn_args = wrapper_fn_params
+            .iter()
+            .zip(&is_argument)
+            .map(|(param, is_argument)| {
+                let binding = &param.binding;
+                if !*is_argument {
+                    captured_param(param)
+                } else if layer.reusable {
+                    Ok(quote! {#binding.cloned()})
+                } else {
+                    Ok(quote! {#binding.take()})
+                }
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let callable = quote! {|| #new_fn_pointer(#(#wrapped_fn_args),*)};
+        // the arguments are taken by the first call, so the callable is turned into an
+        // `impl FnOnce`, and a second call is a compile error instead of a panic
+        let callable = if layer.reusable {
+            callable
+        } else {
+            let call_once_fn = Ident::new("call_once", Span::mixed_site());
+            quote! {
+                {
+                    fn #call_once_fn<R>(f: impl ::core::ops::FnOnce() -> R) -> impl ::core::ops::FnOnce() -> R {
+                        f
+                    }
+                    #call_once_fn(#callable)
+                }
+            }
+        };
+
+        (
+            quote! {#(#argument_declarations)*},
+            quote! {
+                #decorator_fn_path(
+                    #decorator_fn_params
+                    &[#((#argument_names, &#argument_bindings as &dyn ::core::fmt::Debug)),*],
+                    #callable)
+            },
+        )
+    } else if layer.pack_parameters {
+        let packed_params = closure_params.iter();
+        let unpacked_params = closure_params.iter();
+
+        (
+            quote! {},
+            quote! {
+                #decorator_fn_path(
+                    #decorator_fn_params
+                    move |(#(#unpacked_params,)*)| #new_fn_pointer(#(#closure_wrapped_fn_args),*),
+                    (#(#packed_params,)*))
+            },
+        )
+    } else if layer.parameters_override.is_some() {
+        // the closure returns the future of an async wrapped function as it is, so the future is
+        // `Send` and `'static` whenever the future of the wrapped function is
+        (
+            quote! {},
+            quote! {
+                #decorator_fn_path(
+                    #decorator_fn_params
+                    move |#closure_params| #new_fn_pointer(#(#closure_wrapped_fn_args),*),
+                    #closure_params)
+            },
+        )
+    } else {
+        (
+            quote! {},
+            quote! {#decorator_fn_path(#decorator_fn_params #new_fn_pointer, #fn_param_names)},
+        )
+    };
+
+    // the value returned by the decorator function is passed through a function requiring `Send`,
+    // so a future that is not `Send` is reported at the `assert_send` option
+    let decorator_call = match assert_send {
+        Some(span) => {
+            let assert_send_fn = Ident::new("assert_send", Span::mixed_site().located_at(span));
+            quote_spanned! {span=>
+                {
+                    fn #assert_send_fn<T: ::core::marker::Send>(value: T) -> T {
+                        value
+                    }
+                    #assert_send_fn(#decorator_call)
+                }
+            }
+        }
+        None => decorator_call,
+    };
+
+    // the switch is checked before the arguments are moved into the decorator function call
+    let switch_check = match &layer.switch {
+        Some(switch) => {
+            let switch_static = match &switch.name {
+                Some(name) => quote! {::fn_decorator_std::Switch::new(#name)},
+                None => quote! {::fn_decorator_std::Switch::for_fn(#decorated_fn_context)},
+            };
+            let switch_ident = Ident::new("SWITCH", Span::mixed_site());
+            quote_spanned! {switch.span=>
+                static #switch_ident: ::fn_decorator_std::Switch = #switch_static;
+                if !#switch_ident.is_enabled() {
+                    return #new_fn_pointer(#fn_param_names)#decorator_await;
+                }
+            }
+        }
+        None => quote! {},
+    };
+
+    Ok(quote! {
+        #wrapper_fn_attrs
+        #wrapper_fn_vis #wrapper_fn_sig {
+            #wrapper_fn_prelude
+            #wrapped_fn_declaration
+            #self_redeclaration
+            #switch_check
+            #argument_declarations
+
+            #decorator_call #decorator_await
+        }
+    })
+}
+
+fn decorate_impl_block(arg: TokenStream, input: TokenStream) -> TokenStream {
+    let decorate_impl_arg: DecorateImplArg = syn::parse_macro_input!(arg);
+
+    let item: syn::Item = syn::parse_macro_input!(input);
+
+    match item {
+        syn::Item::Impl(item_impl) => decorate_impl_items(decorate_impl_arg, item_impl),
+        syn::Item::Trait(item_trait) => decorate_trait_items(decorate_impl_arg, item_trait),
+        item => Err(syn::Error::new_spanned(
+            item,
+            "`decorate_impl` can only be used on impl blocks and trait definitions",
+        )),
     }
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
+}
+
+/// Decorates the methods of the impl block. Methods of a trait impl cannot have inner functions
+/// next to them, so those are moved to a private helper trait, which is implemented with the
+/// generics and the self type of the impl, so foreign types and blanket impls are supported too.
+fn decorate_impl_items(
+    decorate_impl_arg: DecorateImplArg,
+    mut item_impl: ItemImpl,
+) -> syn::Result<TokenStream2> {
+    let method_names = item_impl
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Fn(method) => Some(method.sig.ident.to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    decorate_impl_arg.check_method_names(&method_names, "impl block")?;
+
+    // impls of a generic trait for the same type differ only in the arguments of the trait, so
+    // the hash of the whole trait path makes the names of the inner functions unique
+    let trait_name = item_impl.trait_.as_ref().map(|(_, trait_path, _)| {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        trait_path.to_token_stream().to_string().hash(&mut hasher);
+        let trait_name = trait_path
+            .segments
+            .last()
+            .map(|segment| to_snake_case(&segment.ident.to_string()))
+            .unwrap_or_default();
+        format!("{trait_name}_{:x}", hasher.finish())
+    });
+
+    let mut inner_fns = Vec::new();
+    for item in item_impl.items.iter_mut() {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
+
+        let attr_count = method.attrs.len();
+        method.attrs.retain(|attr| !is_skip_decorator_attr(attr));
+        let skipped = method.attrs.len() != attr_count;
+
+        // const functions cannot be decorated, so they are skipped, unless they have their own
+        // `use_impl_decorator` attributes
+        let is_const = method.sig.constness.is_some();
+        let mut use_decorator_arg = decorate_impl_arg.use_decorator_arg.clone();
+        if skipped || is_const || !decorate_impl_arg.is_included(&method.sig.ident) {
+            use_decorator_arg.layers.clear();
+        }
+
+        let inner_fn_name_prefix = match &trait_name {
+            Some(trait_name) => format!("{trait_name}_{}", method.sig.ident),
+            None => method.sig.ident.to_string(),
+        };
+        // the inner functions of trait impls are implemented from the helper trait
+        let method_inner_fn_vis = match &item_impl.trait_ {
+            Some(_) => Visibility::Inherited,
+            None => inner_fn_vis(true),
+        };
+        let decorated_fn = decorate_fn(
+            use_decorator_arg,
+            method.clone(),
+            true,
+            &method_inner_fn_vis,
+            &inner_fn_name_prefix,
+        )?;
+
+        if item_impl.trait_.is_some() {
+            *item = ImplItem::Verbatim(decorated_fn.wrapper_fn);
+            inner_fns.push(decorated_fn.inner_fns);
+        } else {
+            *item = ImplItem::Verbatim(decorated_fn.into_token_stream());
+        }
+    }
+
+    let Some((_, trait_path, _)) = &item_impl.trait_ else {
+        return Ok(quote! { #item_impl });
+    };
+    if inner_fns.iter().all(TokenStream2::is_empty) {
+        return Ok(quote! { #item_impl });
+    }
+
+    let inner_fns = syn::parse::Parser::parse2(
+        |input: syn::parse::ParseStream| {
+            let mut inner_fns = Vec::new();
+            while !input.is_empty() {
+                inner_fns.push(input.parse::<ImplItemFn>()?);
+            }
+            Ok(inner_fns)
+        },
+        inner_fns.into_iter().collect(),
+    )?;
+    // the declarations cannot have patterns, and they only need the attributes selecting them
+    let inner_fn_declarations = inner_fns.iter().map(|inner_fn| {
+        let mut sig = inner_fn.sig.clone();
+        for param in sig.inputs.iter_mut() {
+            match param {
+                FnArg::Receiver(receiver) => receiver.mutability = None,
+                FnArg::Typed(param) => *param.pat = syn::parse_quote! { _ },
+            }
+        }
+        let attrs = inner_fn
+            .attrs
+            .iter()
+            .filter(|attr| {
+                DecoratedFnAttrs::SHARED
+                    .iter()
+                    .any(|name| attr.path().is_ident(name))
+            })
+            .collect::<Vec<_>>();
+        quote! {
+            #(#attrs)*
+            #sig;
+        }
+    });
+
+    // the helper trait is named after the impl, so the impls of a module have different ones
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    item_impl
+        .generics
+        .to_token_stream()
+        .to_string()
+        .hash(&mut hasher);
+    trait_path.to_token_stream().to_string().hash(&mut hasher);
+    item_impl
+        .self_ty
+        .to_token_stream()
+        .to_string()
+        .hash(&mut hasher);
+    let trait_ident = trait_path
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+        .unwrap_or_default();
+    let helper_trait_ident = Ident::new(
+        &format!("{trait_ident}FnDecoratorInnerFns{:x}", hasher.finish()),
+        Span::call_site(),
+    );
+
+    let cfg_attrs = item_impl
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .collect::<Vec<_>>();
+    let (impl_generics, ty_generics, where_clause) = item_impl.generics.split_for_impl();
+    let self_ty = &item_impl.self_ty;
+    Ok(quote! {
+        #item_impl
+
+        #(#cfg_attrs)*
+        #[doc(hidden)]
+        trait #helper_trait_ident #impl_generics #where_clause {
+            #(#inner_fn_declarations)*
+        }
+
+        #(#cfg_attrs)*
+        impl #impl_generics #helper_trait_ident #ty_generics for #self_ty #where_clause {
+            #(#inner_fns)*
+        }
+    })
+}
+
+/// Decorates the methods of the trait definition. The inner functions of the decorated methods
+/// cannot be members of the trait, otherwise the implementors could override them, so they are
+/// moved to a private helper trait, which is implemented for every implementor of the trait.
+fn decorate_trait_items(
+    decorate_impl_arg: DecorateImplArg,
+    mut item_trait: syn::ItemTrait,
+) -> syn::Result<TokenStream2> {
+    let method_names = item_trait
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            syn::TraitItem::Fn(method) => Some(method.sig.ident.to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    decorate_impl_arg.check_method_names(&method_names, "trait")?;
+
+    let mut inner_fns = Vec::new();
+    for item in item_trait.items.iter_mut() {
+        let syn::TraitItem::Fn(method) = item else {
+            continue;
+        };
+
+        let attr_count = method.attrs.len();
+        method.attrs.retain(|attr| !is_skip_decorator_attr(attr));
+        let skipped = method.attrs.len() != attr_count;
+
+        let mut use_decorator_arg = decorate_impl_arg.use_decorator_arg.clone();
+        if skipped || !decorate_impl_arg.is_included(&method.sig.ident) {
+            use_decorator_arg.layers.clear();
+        }
+
+        let decorated_fn = match &method.default {
+            Some(block) => {
+                let method_impl = ImplItemFn {
+                    attrs: method.attrs.clone(),
+                    vis: Visibility::Inherited,
+                    defaultness: None,
+                    sig: method.sig.clone(),
+                    block: block.clone(),
+                };
+                let inner_fn_name_prefix = method.sig.ident.to_string();
+                decorate_fn(
+                    use_decorator_arg,
+                    method_impl,
+                    true,
+                    &Visibility::Inherited,
+                    &inner_fn_name_prefix,
+                )?
+            }
+            // undecorated declarations are not renamed
+            None if use_decorator_arg.layers.is_empty()
+                && !method.attrs.iter().any(is_use_decorator_attr) =>
+            {
+                continue;
+            }
+            None => decorate_required_trait_fn(
+                use_decorator_arg,
+                method.clone(),
+                &Visibility::Inherited,
+            )?,
+        };
+        *item = syn::TraitItem::Verbatim(decorated_fn.wrapper_fn);
+        inner_fns.push(decorated_fn.inner_fns);
+    }
+
+    if inner_fns.iter().all(TokenStream2::is_empty) {
+        return Ok(quote! { #item_trait });
+    }
+
+    let helper_trait_ident = Ident::new(
+        &format!("{}FnDecoratorInnerFns", item_trait.ident),
+        Span::call_site(),
+    );
+    let self_ident = Ident::new("FnDecoratorSelf", Span::mixed_site());
+    let trait_ident = &item_trait.ident;
+    let cfg_attrs = item_trait
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .collect::<Vec<_>>();
+    let (_, ty_generics, where_clause) = item_trait.generics.split_for_impl();
+    let mut helper_impl_generics = item_trait.generics.clone();
+    helper_impl_generics.params.push(
+        syn::parse_quote! { #self_ident: ?::core::marker::Sized + #trait_ident #ty_generics },
+    );
+    let (helper_impl_generics, _, _) = helper_impl_generics.split_for_impl();
+    let helper_trait_generics = &item_trait.generics;
+    Ok(quote! {
+        #item_trait
+
+        #(#cfg_attrs)*
+        #[doc(hidden)]
+        trait #helper_trait_ident #helper_trait_generics: #trait_ident #ty_generics #where_clause {
+            #(#inner_fns)*
+        }
+
+        #(#cfg_attrs)*
+        impl #helper_impl_generics #helper_trait_ident #ty_generics for #self_ident #where_clause {}
+    })
+}
+
+fn decorate_module_block(arg: TokenStream, input: TokenStream) -> TokenStream {
+    let decorate_module_arg: DecorateModuleArg = syn::parse_macro_input!(arg);
+
+    let item_mod: syn::ItemMod = syn::parse_macro_input!(input);
+
+    decorate_inline_module(decorate_module_arg, item_mod)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn decorate_inline_module(
+    decorate_module_arg: DecorateModuleArg,
+    mut item_mod: syn::ItemMod,
+) -> syn::Result<TokenStream2> {
+    let Some((_, items)) = &mut item_mod.content else {
+        return Err(syn::Error::new_spanned(
+            &item_mod,
+            "only inline modules can be decorated",
+        ));
+    };
+
+    let mut fn_names = Vec::new();
+    decorate_module_items(&decorate_module_arg, items, &mut fn_names)?;
+
+    // names without `*` have to name a function, like the names given to `decorate_impl`
+    for pattern in decorate_module_arg
+        .include
+        .iter()
+        .chain(decorate_module_arg.exclude.iter())
+        .flat_map(|list| list.0.iter())
+        .filter(|pattern| !pattern.pattern.contains('*'))
+    {
+        if !fn_names.contains(&pattern.pattern) {
+            return Err(unknown_name_error(
+                &Ident::new(&pattern.pattern, pattern.span),
+                format!("no function named `{}` in this module", pattern.pattern),
+                fn_names.iter().map(String::as_str),
+            ));
+        }
+    }
+
+    Ok(quote! { #item_mod })
+}
+
+/// Decorates the functions of a module, and the methods of its impl blocks and the functions of
+/// its nested modules if the decoration is recursive. The names of the visited functions are
+/// collected into `fn_names`.
+fn decorate_module_items(
+    decorate_module_arg: &DecorateModuleArg,
+    items: &mut [syn::Item],
+    fn_names: &mut Vec<String>,
+) -> syn::Result<()> {
+    let is_decorated = |attrs: &mut Vec<Attribute>, vis: &Visibility, sig: &Signature| {
+        let attr_count = attrs.len();
+        attrs.retain(|attr| !is_skip_decorator_attr(attr));
+        let skipped = attrs.len() != attr_count;
+
+        // const functions cannot be decorated, so they are skipped, unless they have their own
+        // `use_decorator` attributes
+        let is_const = sig.constness.is_some();
+
+        let name = sig.ident.unraw().to_string();
+        let included = decorate_module_arg
+            .include
+            .as_ref()
+            .map(|include| include.matches(&name))
+            .unwrap_or(true);
+        let excluded = decorate_module_arg
+            .exclude
+            .as_ref()
+            .map(|exclude| exclude.matches(&name))
+            .unwrap_or(false);
+        // `pub(self)` is the same as the inherited visibility
+        let is_pub = match vis {
+            Visibility::Public(_) => true,
+            Visibility::Restricted(restricted) => !restricted.path.is_ident("self"),
+            Visibility::Inherited => false,
+        };
+        let visible = !decorate_module_arg.only_pub || is_pub;
+
+        !skipped && !is_const && included && !excluded && visible
+    };
+
+    for item in items.iter_mut() {
+        match item {
+            syn::Item::Fn(item_fn) => {
+                fn_names.push(item_fn.sig.ident.unraw().to_string());
+
+                let mut use_decorator_arg = decorate_module_arg.use_decorator_arg.clone();
+                if !is_decorated(&mut item_fn.attrs, &item_fn.vis, &item_fn.sig) {
+                    use_decorator_arg.layers.clear();
+                }
+
+                let item_impl = ImplItemFn {
+                    attrs: item_fn.attrs.clone(),
+                    vis: item_fn.vis.clone(),
+                    defaultness: None,
+                    sig: item_fn.sig.clone(),
+                    block: (*item_fn.block).clone(),
+                };
+                let inner_fn_name_prefix = item_impl.sig.ident.to_string();
+                let decorated_fn = decorate_fn(
+                    use_decorator_arg,
+                    item_impl,
+                    false,
+                    &inner_fn_vis(false),
+                    &inner_fn_name_prefix,
+                )?;
+                *item = syn::Item::Verbatim(decorated_fn.into_token_stream());
+            }
+            syn::Item::Impl(item_impl) if decorate_module_arg.recursive => {
+                let attr_count = item_impl.attrs.len();
+                item_impl.attrs.retain(|attr| !is_skip_decorator_attr(attr));
+                if item_impl.attrs.len() != attr_count {
+                    continue;
+                }
+
+                // the methods of trait impls are as visible as the trait
+                let is_trait_impl = item_impl.trait_.is_some();
+                let mut include = Vec::new();
+                for impl_item in item_impl.items.iter_mut() {
+                    if let ImplItem::Fn(method) = impl_item {
+                        fn_names.push(method.sig.ident.unraw().to_string());
+                        let vis = match is_trait_impl {
+                            true => Visibility::Public(Default::default()),
+                            false => method.vis.clone(),
+                        };
+                        if is_decorated(&mut method.attrs, &vis, &method.sig) {
+                            include.push(method.sig.ident.clone());
+                        }
+                    }
+                }
+
+                let decorate_impl_arg = DecorateImplArg {
+                    use_decorator_arg: decorate_module_arg.use_decorator_arg.clone(),
+                    include: Some(NameList(include)),
+                    exclude: None,
+                };
+                *item =
+                    syn::Item::Verbatim(decorate_impl_items(decorate_impl_arg, item_impl.clone())?);
+            }
+            syn::Item::Mod(item_mod) if decorate_module_arg.recursive => {
+                let attr_count = item_mod.attrs.len();
+                item_mod.attrs.retain(|attr| !is_skip_decorator_attr(attr));
+                if item_mod.attrs.len() != attr_count {
+                    continue;
+                }
 
-    tokens.into()
+                if let Some((_, items)) = &mut item_mod.content {
+                    decorate_module_items(decorate_module_arg, items, fn_names)?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake_case = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i != 0 {
+                snake_case.push('_');
+            }
+            snake_case.extend(c.to_lowercase());
+        } else {
+            snake_case.push(c);
+        }
+    }
+    snake_case
+}
+
+/// The arguments of the `decorate!` macro, the decorator options are followed by the closure.
+struct DecorateArg {
+    use_decorator_arg: UseDecoratorArg,
+    closure: syn::ExprClosure,
+}
+
+impl Parse for DecorateArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // the options end at the first comma followed by a closure, the parameters of the closure
+        // can be separated by commas too
+        let is_closure_next = |input: syn::parse::ParseStream| {
+            input.peek(Token![,])
+                && (input.peek2(Token![|])
+                    || input.peek2(Token![||])
+                    || input.peek2(Token![move])
+                    || input.peek2(Token![async])
+                    || input.peek2(Token![static]))
+        };
+        let mut options = TokenStream2::new();
+        while !input.is_empty() && !is_closure_next(input) {
+            options.extend([input.parse::<proc_macro2::TokenTree>()?]);
+        }
+        if input.is_empty() {
+            return Err(input.error("expected a closure after the decorator options"));
+        }
+        input.parse::<Token![,]>()?;
+        let closure = input.parse()?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(Self {
+            use_decorator_arg: syn::parse2(options)?,
+            closure,
+        })
+    }
+}
+
+fn decorate_closure(decorate_arg: DecorateArg) -> syn::Result<TokenStream2> {
+    let DecorateArg {
+        use_decorator_arg,
+        closure,
+    } = decorate_arg;
+
+    if use_decorator_arg.debug || use_decorator_arg.assert_send.is_some() {
+        return Err(syn::Error::new_spanned(
+            closure.or1_token,
+            "`debug` and `assert_send` are not supported for closures",
+        ));
+    }
+    for layer in &use_decorator_arg.layers {
+        if layer.pack_parameters
+            || layer.with_context
+            || layer.with_arguments
+            || layer.mode.is_some()
+            || layer.when.is_some()
+            || layer.switch.is_some()
+        {
+            return Err(syn::Error::new_spanned(
+                &layer.decorator_function_call.middleware_fn_path,
+                "only `hide_parameters`, `exact_parameters`, `reusable` and `override_return_type` are supported for closures",
+            ));
+        }
+    }
+
+    // the parameters are bound like the ones of wrapper functions, the types are kept if given
+    let params = closure
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(index, param)| {
+            let (pat, ty) = match param {
+                Pat::Type(pat_type) => (pat_type.pat.as_ref(), Some(&pat_type.ty)),
+                pat => (pat, None),
+            };
+            let param = match pat {
+                Pat::Ident(pat_ident)
+                    if pat_ident.by_ref.is_none() && pat_ident.subpat.is_none() =>
+                {
+                    WrapperFnParam {
+                        name: Some(pat_ident.ident.to_string()),
+                        binding: pat_ident.ident.clone(),
+                        is_mut_ref: false,
+                    }
+                }
+                _ => WrapperFnParam {
+                    name: None,
+                    binding: Ident::new(&format!("arg_{index}"), Span::mixed_site()),
+                    is_mut_ref: false,
+                },
+            };
+            let binding = &param.binding;
+            let typed_binding = match ty {
+                Some(ty) => quote! {#binding: #ty},
+                None => quote! {#binding},
+            };
+
+            (param, typed_binding)
+        })
+        .collect::<Vec<_>>();
+    let typed_bindings = params
+        .iter()
+        .map(|(_, typed_binding)| typed_binding)
+        .collect::<Vec<_>>();
+    let bindings = params
+        .iter()
+        .map(|(param, _)| &param.binding)
+        .collect::<Vec<_>>();
+
+    // every layer is a closure wrapping the previous one, the wrapped closure is moved into the
+    // decorator function call, so it does not have to be `Clone`. Reusable layers clone it for
+    // every call instead, so they can be called more than once if the wrapped closure can be
+    let wrapped_closure = Ident::new("wrapped_closure", Span::mixed_site());
+    let mut layers = Vec::new();
+    for layer in use_decorator_arg.layers.iter().rev() {
+        let decorator_fn_path = &layer.decorator_function_call.middleware_fn_path;
+        let decorator_fn_params = &layer.decorator_function_call.middleware_params;
+        let decorator_fn_params = if decorator_fn_params.is_empty() {
+            quote! {}
+        } else {
+            quote! {#decorator_fn_params,}
+        };
+        let return_type = match &layer.override_return_type {
+            Some(override_return_type) => quote! {-> #override_return_type},
+            None => quote! {},
+        };
+        let callee = if layer.reusable {
+            quote! {::core::clone::Clone::clone(&#wrapped_closure)}
+        } else {
+            quote! {#wrapped_closure}
+        };
+
+        let decorator_call = match &layer.parameters_override {
+            Some(parameters_override) => {
+                let param_names = params.iter().filter_map(|(param, _)| param.name.as_deref());
+                for name in parameters_override.names() {
+                    if !param_names.clone().any(|param_name| name == param_name) {
+                        return Err(unknown_name_error(
+                            name,
+                            format!("no parameter named `{name}` in the decorated closure"),
+                            param_names.clone(),
+                        ));
+                    }
+                }
+
+                let passed_bindings = params
+                    .iter()
+                    .filter(|(param, _)| match (parameters_override, &param.name) {
+                        (ParametersOverride::Hide(_), None) => true,
+                        (ParametersOverride::Exact(_), None) => false,
+                        (ParametersOverride::Hide(hide_parameters_list), Some(name)) => {
+                            !hide_parameters_list.0.iter().any(|hidden| hidden == name)
+                        }
+                        (ParametersOverride::Exact(exact_parameters_list), Some(name)) => {
+                            exact_parameters_list.0.iter().any(|exact| exact == name)
+                        }
+                    })
+                    .map(|(param, _)| &param.binding)
+                    .collect::<Vec<_>>();
+
+                let callee_closure = if layer.reusable {
+                    quote! {
+                        {
+                            let #wrapped_closure = #callee;
+                            move |#(#passed_bindings),*| #wrapped_closure(#(#bindings),*)
+                        }
+                    }
+                } else {
+                    quote! {move |#(#passed_bindings),*| #wrapped_closure(#(#bindings),*)}
+                };
+
+                quote! {
+                    #decorator_fn_path(
+                        #decorator_fn_params
+                        #callee_closure,
+                        #(#passed_bindings),*)
+                }
+            }
+            None => quote! {
+                #decorator_fn_path(#decorator_fn_params #callee, #(#bindings),*)
+            },
+        };
+
+        layers.push(quote! {
+            let #wrapped_closure = move |#(#typed_bindings),*| #return_type { #decorator_call };
+        });
+    }
+
+    Ok(quote! {
+        {
+            let #wrapped_closure = #closure;
+            #(#layers)*
+            #wrapped_closure
+        }
+    })
+}
+
+fn decorator_impl(arg: TokenStream, input: TokenStream) -> TokenStream {
+    let arg = TokenStream2::from(arg);
+    if !arg.is_empty() {
+        return syn::Error::new_spanned(arg, "`decorator` does not have parameters")
+            .to_compile_error()
+            .into();
+    }
+
+    let mut decorator_fn: ItemFn = syn::parse_macro_input!(input);
+    if let Err(e) = unpack_next_parameter(&mut decorator_fn) {
+        return e.to_compile_error().into();
+    }
+
+    quote! { #decorator_fn }.into()
+}
+
+/// Replaces the trailing `Next<Args, R>` parameter of a decorator function with the decorated
+/// function and its packed arguments, which is how `pack_parameters` passes them, then rebuilds
+/// the `Next` handle at the beginning of the function body.
+fn unpack_next_parameter(decorator_fn: &mut ItemFn) -> syn::Result<()> {
+    let next_param = match decorator_fn
+        .sig
+        .inputs
+        .pop()
+        .map(|param| param.into_value())
+    {
+        Some(FnArg::Typed(next_param)) => next_param,
+        Some(param) => {
+            return Err(syn::Error::new_spanned(
+                param,
+                "the last parameter of a decorator must be a `Next<Args, R>`",
+            ))
+        }
+        None => {
+            return Err(syn::Error::new_spanned(
+                &decorator_fn.sig,
+                "the last parameter of a decorator must be a `Next<Args, R>`",
+            ))
+        }
+    };
+
+    let mut next_type = match next_param.ty.as_ref() {
+        syn::Type::Path(next_type) => next_type.clone(),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &next_param.ty,
+                "the last parameter of a decorator must be a `Next<Args, R>`",
+            ))
+        }
+    };
+    let next_type_args = match next_type.path.segments.last_mut() {
+        Some(segment) if segment.ident == "Next" => match &mut segment.arguments {
+            syn::PathArguments::AngleBracketed(args) if args.args.len() == 2 => &mut args.args,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    segment,
+                    "expected the arguments and the return type as `Next<Args, R>`",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &next_param.ty,
+                "the last parameter of a decorator must be a `Next<Args, R>`",
+            ))
+        }
+    };
+    let (args_type, return_type) = match (&next_type_args[0], &next_type_args[1]) {
+        (syn::GenericArgument::Type(args_type), syn::GenericArgument::Type(return_type)) => {
+            (args_type.clone(), return_type.clone())
+        }
+        _ => {
+            return Err(syn::Error::new_spanned(
+                next_type_args,
+                "expected the arguments and the return type as `Next<Args, R>`",
+            ))
+        }
+    };
+
+    let next_fn_type = Ident::new("__FnDecoratorNextFn", Span::call_site());
+    next_type_args.push(syn::parse_quote! { #next_fn_type });
+    decorator_fn
+        .sig
+        .generics
+        .params
+        .push(syn::parse_quote! { #next_fn_type: ::core::ops::FnOnce(#args_type) -> #return_type });
+
+    let next_fn = Ident::new("__fn_decorator_next_fn", Span::mixed_site());
+    let next_args = Ident::new("__fn_decorator_next_args", Span::mixed_site());
+    decorator_fn
+        .sig
+        .inputs
+        .push(syn::parse_quote! { #next_fn: #next_fn_type });
+    decorator_fn
+        .sig
+        .inputs
+        .push(syn::parse_quote! { #next_args: #args_type });
+
+    let next_pat = &next_param.pat;
+    decorator_fn.block.stmts.insert(
+        0,
+        syn::parse_quote! { let #next_pat: #next_type = <#next_type>::new(#next_fn, #next_args); },
+    );
+
+    Ok(())
+}
+
+#[proc_macro]
+pub fn decorate(input: TokenStream) -> TokenStream {
+    let decorate_arg: DecorateArg = syn::parse_macro_input!(input);
+
+    decorate_closure(decorate_arg)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_attribute]
+pub fn decorate_impl(arg: TokenStream, input: TokenStream) -> TokenStream {
+    decorate_impl_block(arg, input)
+}
+
+#[proc_macro_attribute]
+pub fn decorate_module(arg: TokenStream, input: TokenStream) -> TokenStream {
+    decorate_module_block(arg, input)
+}
+
+#[proc_macro_attribute]
+pub fn decorator(arg: TokenStream, input: TokenStream) -> TokenStream {
+    decorator_impl(arg, input)
 }
 
 #[proc_macro_attribute]
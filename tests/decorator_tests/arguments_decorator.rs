@@ -0,0 +1,54 @@
+use fn_decorator::{use_decorator, use_impl_decorator};
+use fn_decorator_std::{format_call, Arguments};
+
+fn audit(name: &str, arguments: &Arguments, f: impl FnOnce() -> u64) -> String {
+    let call = format_call(name, arguments);
+    format!("{call} = {}", f())
+}
+
+async fn audit_async<FutureType: std::future::Future<Output = u64>>(
+    arguments: &Arguments<'_>,
+    f: impl FnOnce() -> FutureType,
+) -> String {
+    let call = format_call("transfer", arguments);
+    format!("{call} = {}", f().await)
+}
+
+#[use_decorator(
+    audit("transfer"),
+    with_arguments,
+    hide_parameters = [password],
+    override_return_type = String,
+)]
+fn transfer(from: String, to: &str, amount: u64, password: &str) -> u64 {
+    assert_eq!(password, "secret");
+    drop(from);
+    amount - to.len() as u64
+}
+
+#[derive(Debug)]
+struct Account(u64);
+
+impl Account {
+    #[use_impl_decorator(audit_async(), with_arguments, override_return_type = String)]
+    async fn transfer(&mut self, amount: u64) -> u64 {
+        self.0 -= amount;
+        self.0
+    }
+}
+
+#[test]
+fn arguments_decorator() {
+    let result = transfer("alice".into(), "bob", 10, "secret");
+    assert_eq!(
+        result,
+        r#"transfer(from = "alice", to = "bob", amount = 10) = 7"#
+    );
+}
+
+#[tokio::test]
+async fn async_method_arguments_decorator() {
+    let mut account = Account(10);
+    let result = account.transfer(3).await;
+    assert_eq!(result, "transfer(self = Account(10), amount = 3) = 7");
+}
@@ -0,0 +1,45 @@
+use fn_decorator::{use_decorator, use_impl_decorator};
+
+fn decorator(f: fn(i64) -> i64, x: i64) -> i64 {
+    f(x) + 1
+}
+
+/// Returns the given number.
+#[use_decorator(decorator())]
+#[cfg(not(any()))]
+#[must_use]
+#[deprecated = "use `add_1` instead"]
+fn get(x: i64) -> i64 {
+    x
+}
+
+#[use_decorator(decorator())]
+#[cfg(any())]
+fn get(x: i64) -> i64 {
+    x + 1
+}
+
+struct Number(i64);
+
+fn number_decorator(f: fn(&Number) -> i64, number: &Number) -> i64 {
+    f(number) + 1
+}
+
+impl Number {
+    #[use_impl_decorator(number_decorator())]
+    #[inline]
+    #[allow(clippy::inherent_to_string)]
+    fn to_string(&self) -> i64 {
+        self.0
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn attributes_decorator() {
+    let result = get(1);
+    assert_eq!(result, 2);
+
+    let result = Number(1).to_string();
+    assert_eq!(result, 2);
+}
@@ -0,0 +1,69 @@
+use std::{fmt::Debug, sync::Mutex};
+
+use fn_decorator::decorate;
+
+fn add_one(f: impl FnOnce(i64) -> i64, x: i64) -> i64 {
+    f(x) + 1
+}
+
+fn double_result(f: impl FnOnce() -> i64) -> i64 {
+    f() * 2
+}
+
+fn debug_format<R: Debug>(f: impl FnOnce() -> R) -> String {
+    format!("{:?}", f())
+}
+
+fn run_callbacks(callbacks: &[Box<dyn Fn(i64) -> i64>], x: i64) -> Vec<i64> {
+    callbacks.iter().map(|callback| callback(x)).collect()
+}
+
+#[test]
+fn closure_decorator() {
+    let offset = 10;
+    let callbacks: Vec<Box<dyn Fn(i64) -> i64>> = vec![
+        Box::new(decorate!(add_one(), |x: i64| x * 2)),
+        Box::new(decorate!(
+            double_result(),
+            exact_parameters = [],
+            move |x: i64| x + offset
+        )),
+        Box::new(decorate!(add_one(), add_one(), |x| x)),
+    ];
+    assert_eq!(run_callbacks(&callbacks, 1), vec![3, 22, 3]);
+
+    let greet = decorate!(
+        debug_format(),
+        hide_parameters = [greeting, name],
+        override_return_type = String,
+        |greeting: &str, name: &str| format!("{greeting} {name}"),
+    );
+    assert_eq!(greet("Hello", "world"), "\"Hello world\"");
+}
+
+#[test]
+fn non_clone_closure_decorator() {
+    // the closure is moved into the decorator function, so it does not have to be `Clone`
+    let total = Mutex::new(1);
+    let add_to_total = decorate!(add_one(), move |x: i64| {
+        let mut total = total.lock().unwrap();
+        *total += x;
+        *total
+    });
+    assert_eq!(add_to_total(1), 3);
+}
+
+#[test]
+fn reusable_closure_decorator() {
+    // the closure is cloned for every call, so it can be called more than once
+    let suffix = String::from("!");
+    let shout = decorate!(
+        debug_format(),
+        exact_parameters = [],
+        reusable,
+        override_return_type = String,
+        move |text: &str| format!("{text}{suffix}"),
+    );
+    assert_eq!(shout("Hello"), "\"Hello!\"");
+    assert_eq!(shout("Bye"), "\"Bye!\"");
+}
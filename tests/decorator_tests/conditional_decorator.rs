@@ -0,0 +1,65 @@
+use fn_decorator::{decorate_impl, use_decorator, use_impl_decorator};
+
+fn add_one(f: impl FnOnce(i64) -> i64, x: i64) -> i64 {
+    f(x) + 1
+}
+
+// only used by a disabled layer, which does not exist in the build
+#[allow(dead_code)]
+fn double(f: fn(i64) -> i64, x: i64) -> i64 {
+    f(x) * 2
+}
+
+#[use_decorator(add_one(), when = cfg(all()))]
+fn enabled(x: i64) -> i64 {
+    x
+}
+
+#[use_decorator(add_one(), when = cfg(any()))]
+fn disabled(x: i64) -> i64 {
+    x
+}
+
+#[use_decorator(double(), when = cfg(any()))]
+#[use_decorator(add_one(), when = cfg(not(any())))]
+#[use_decorator(add_one())]
+fn stacked(x: i64) -> i64 {
+    x
+}
+
+struct Number(i64);
+
+impl Number {
+    #[use_impl_decorator(add_one(), hide_parameters = [self], when = cfg(any()))]
+    fn add_disabled(&self, x: i64) -> i64 {
+        self.0 + x
+    }
+
+    #[use_impl_decorator(add_one(), hide_parameters = [self], when = cfg(all()))]
+    fn add_enabled(&self, x: i64) -> i64 {
+        self.0 + x
+    }
+}
+
+trait Add {
+    fn add(&self, x: i64) -> i64;
+}
+
+#[decorate_impl(add_one(), hide_parameters = [self], when = cfg(all()))]
+impl Add for Number {
+    fn add(&self, x: i64) -> i64 {
+        self.0 + x
+    }
+}
+
+#[test]
+fn conditional_decorator() {
+    assert_eq!(enabled(1), 2);
+    assert_eq!(disabled(1), 1);
+    assert_eq!(stacked(1), 3);
+
+    let number = Number(1);
+    assert_eq!(number.add_disabled(1), 2);
+    assert_eq!(number.add_enabled(1), 3);
+    assert_eq!(Add::add(&number, 1), 3);
+}
@@ -0,0 +1,58 @@
+use fn_decorator::{use_decorator, use_impl_decorator};
+use fn_decorator_std::FnContext;
+
+fn describe(context: &'static FnContext, f: impl FnOnce() -> i64) -> String {
+    format!("{context} = {}", f())
+}
+
+async fn describe_async<FutureType: std::future::Future<Output = i64>>(
+    context: &'static FnContext,
+    f: impl FnOnce() -> FutureType,
+) -> (&'static FnContext, i64) {
+    (context, f().await)
+}
+
+#[use_decorator(describe(), with_context, exact_parameters = [], override_return_type = String)]
+fn get_1() -> i64 {
+    1
+}
+
+struct Number(i64);
+
+impl Number {
+    #[use_impl_decorator(
+        describe_async(),
+        with_context,
+        exact_parameters = [],
+        override_return_type = (&'static FnContext, i64),
+    )]
+    async fn get(&self) -> i64 {
+        self.0
+    }
+}
+
+#[test]
+fn context_decorator() {
+    let result = get_1();
+    assert_eq!(
+        result,
+        "tests::decorator_tests::context_decorator::get_1 = 1"
+    );
+}
+
+#[tokio::test]
+async fn async_method_context_decorator() {
+    let (context, result) = Number(2).get().await;
+    assert_eq!(result, 2);
+    assert_eq!(
+        *context,
+        FnContext {
+            name: "get",
+            module_path: "tests::decorator_tests::context_decorator",
+            file: file!(),
+            line: 29,
+            is_async: true,
+            is_method: true,
+        }
+    );
+}
@@ -1,16 +1,54 @@
-// use fn_decorator::use_decorator;
-
-// fn decorator(f: fn() -> i64) -> i64 {
-//     f() + 1
-// }
-
-// #[use_decorator(decorator(), debug)]
-// fn get_1() -> i64 {
-//     1
-// }
-
-// #[test]
-// fn debug_fn_decorator() {
-//     let result = get_1();
-//     assert_eq!(result, 2);
-// }
+use fn_decorator::{use_decorator, use_impl_decorator};
+
+fn decorator(f: fn() -> i64) -> i64 {
+    f() + 1
+}
+
+#[use_decorator(decorator(), debug)]
+fn get_1_debug() -> i64 {
+    1
+}
+
+struct A;
+
+impl A {
+    #[use_impl_decorator(decorator(), debug)]
+    fn get_debug() -> i64 {
+        1
+    }
+}
+
+struct B;
+
+impl B {
+    #[use_impl_decorator(decorator(), debug)]
+    fn get_debug() -> i64 {
+        2
+    }
+}
+
+#[test]
+fn debug_fn_decorator() {
+    let result = get_1_debug();
+    assert_eq!(result, 2);
+    assert_eq!(A::get_debug(), 2);
+    assert_eq!(B::get_debug(), 3);
+
+    let target_dir = option_env!("CARGO_TARGET_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("target"));
+    let read_expansion = |file_name: &str| {
+        std::fs::read_to_string(
+            target_dir
+                .join("fn_decorator")
+                .join(env!("CARGO_CRATE_NAME"))
+                .join(file_name),
+        )
+        .unwrap()
+    };
+
+    // the files are named after the function and the line of its name
+    assert!(read_expansion("get_1_debug_8.rs").contains("fn get_1_debug() -> i64 {"));
+    assert!(read_expansion("get_debug_16.rs").contains("-> i64 {\n    1\n}"));
+    assert!(read_expansion("get_debug_25.rs").contains("-> i64 {\n    2\n}"));
+}
@@ -0,0 +1,83 @@
+use fn_decorator::decorate_impl;
+
+fn add_one(f: impl FnOnce() -> i64) -> i64 {
+    f() + 1
+}
+
+fn double(f: fn(i64) -> i64, x: i64) -> i64 {
+    f(x) * 2
+}
+
+struct MyStruct {
+    x: i64,
+}
+
+#[decorate_impl(add_one(), exact_parameters = [])]
+impl MyStruct {
+    fn get(&self) -> i64 {
+        self.x
+    }
+
+    fn add(&self, y: i64) -> i64 {
+        self.x + y
+    }
+
+    #[use_impl_decorator(double())]
+    fn triple(x: i64) -> i64 {
+        x * 3
+    }
+
+    #[skip_decorator]
+    fn get_undecorated(&self) -> i64 {
+        self.x
+    }
+
+    // const functions cannot be decorated, they are skipped
+    const fn new(x: i64) -> Self {
+        Self { x }
+    }
+}
+
+struct Filtered;
+
+#[decorate_impl(add_one(), exact_parameters = [], exclude = [get_2])]
+impl Filtered {
+    fn get_1() -> i64 {
+        1
+    }
+
+    fn get_2() -> i64 {
+        2
+    }
+}
+
+#[decorate_impl(include = [get_4], add_one(), exact_parameters = [])]
+impl Filtered {
+    fn get_3() -> i64 {
+        3
+    }
+
+    fn get_4() -> i64 {
+        4
+    }
+}
+
+#[test]
+fn decorate_impl() {
+    let obj = MyStruct { x: 1 };
+    assert_eq!(obj.get(), 2);
+    assert_eq!(obj.add(1), 3);
+    assert_eq!(MyStruct::triple(1), 7);
+    assert_eq!(obj.get_undecorated(), 1);
+
+    const OBJ: MyStruct = MyStruct::new(2);
+    assert_eq!(OBJ.get(), 3);
+}
+
+#[test]
+fn decorate_impl_with_filters() {
+    assert_eq!(Filtered::get_1(), 2);
+    assert_eq!(Filtered::get_2(), 2);
+    assert_eq!(Filtered::get_3(), 3);
+    assert_eq!(Filtered::get_4(), 5);
+}
@@ -0,0 +1,123 @@
+use fn_decorator::decorate_module;
+
+fn add_one(f: impl FnOnce() -> i64) -> i64 {
+    f() + 1
+}
+
+fn double(f: fn(i64) -> i64, x: i64) -> i64 {
+    f(x) * 2
+}
+
+#[decorate_module(add_one(), exact_parameters = [], only = pub, exclude = [helper_*])]
+mod api {
+    use super::{add_one, double};
+
+    pub fn get_1() -> i64 {
+        1
+    }
+
+    pub fn add(x: i64, y: i64) -> i64 {
+        x + y + helper_offset() + private_offset() + self_offset()
+    }
+
+    #[use_decorator(double())]
+    pub fn triple(x: i64) -> i64 {
+        x * 3
+    }
+
+    #[skip_decorator]
+    pub fn get_undecorated() -> i64 {
+        1
+    }
+
+    pub fn helper_offset() -> i64 {
+        10
+    }
+
+    fn private_offset() -> i64 {
+        100
+    }
+
+    // `pub(self)` is private, so it is not decorated with `only = pub`
+    #[allow(clippy::needless_pub_self)]
+    pub(self) fn self_offset() -> i64 {
+        1000
+    }
+
+    // const functions cannot be decorated, they are skipped
+    pub const fn get_const() -> i64 {
+        1
+    }
+
+    pub struct Counter(pub i64);
+
+    impl Counter {
+        pub fn get(&self) -> i64 {
+            self.0
+        }
+    }
+}
+
+// the decorator function calls are resolved in the nested modules too
+#[decorate_module(
+    crate::decorator_tests::decorate_module::add_one(),
+    exact_parameters = [],
+    recursive,
+    include = [get*],
+)]
+mod nested {
+    pub fn get_2() -> i64 {
+        2
+    }
+
+    pub fn other() -> i64 {
+        0
+    }
+
+    pub struct Counter(pub i64);
+
+    impl Counter {
+        pub fn get(&self) -> i64 {
+            self.0
+        }
+    }
+
+    impl std::ops::Deref for Counter {
+        type Target = i64;
+
+        fn deref(&self) -> &i64 {
+            &self.0
+        }
+    }
+
+    pub mod inner {
+        pub fn get_3() -> i64 {
+            3
+        }
+    }
+
+    #[skip_decorator]
+    pub mod skipped {
+        pub fn get_4() -> i64 {
+            4
+        }
+    }
+}
+
+#[test]
+fn decorate_module() {
+    assert_eq!(api::get_1(), 2);
+    assert_eq!(api::add(1, 2), 1114);
+    assert_eq!(api::triple(1), 7);
+    assert_eq!(api::get_undecorated(), 1);
+    assert_eq!(api::helper_offset(), 10);
+    assert_eq!(api::get_const(), 1);
+    assert_eq!(api::Counter(5).get(), 5);
+
+    assert_eq!(nested::get_2(), 3);
+    assert_eq!(nested::other(), 0);
+    assert_eq!(nested::Counter(5).get(), 6);
+    assert_eq!(*nested::Counter(5), 5);
+    assert_eq!(nested::inner::get_3(), 4);
+    assert_eq!(nested::skipped::get_4(), 4);
+}
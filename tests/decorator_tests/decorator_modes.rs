@@ -0,0 +1,54 @@
+use std::future::Future;
+
+use fn_decorator::use_decorator;
+
+async fn spawn_blocking(f: fn(i64) -> i64, x: i64) -> i64 {
+    tokio::task::spawn_blocking(move || f(x)).await.unwrap()
+}
+
+fn block_on<FutureType: Future<Output = i64>>(f: fn(i64) -> FutureType, x: i64) -> i64 {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(f(x))
+}
+
+fn add_one_later<FutureType: Future<Output = i64>>(
+    f: impl FnOnce() -> FutureType,
+) -> impl Future<Output = i64> {
+    let future = f();
+    async move { future.await + 1 }
+}
+
+#[use_decorator(spawn_blocking(), mode = async)]
+fn double(x: i64) -> i64 {
+    x * 2
+}
+
+#[use_decorator(block_on(), mode = sync)]
+async fn triple(x: i64) -> i64 {
+    x * 3
+}
+
+#[use_decorator(add_one_later(), exact_parameters = [], mode = future)]
+async fn get_1() -> i64 {
+    1
+}
+
+#[tokio::test]
+async fn async_decorator_of_sync_fn() {
+    let result = double(2).await;
+    assert_eq!(result, 4);
+}
+
+#[test]
+fn sync_decorator_of_async_fn() {
+    let result = triple(2);
+    assert_eq!(result, 6);
+}
+
+#[tokio::test]
+async fn future_decorator_of_async_fn() {
+    let result = get_1().await;
+    assert_eq!(result, 2);
+}
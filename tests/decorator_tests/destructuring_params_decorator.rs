@@ -0,0 +1,62 @@
+use fn_decorator::{use_decorator, use_impl_decorator};
+
+fn decorator(
+    f: fn((i64, i64), Point, i64, u8) -> i64,
+    a: (i64, i64),
+    p: Point,
+    x: i64,
+    y: u8,
+) -> i64 {
+    f(a, p, x, y) + 1
+}
+
+fn hiding_decorator(f: impl FnOnce(i64) -> i64, x: i64) -> i64 {
+    f(x + 1)
+}
+
+fn point_decorator(f: impl FnOnce(Point) -> i64, p: Point) -> i64 {
+    f(Point { x: p.x + 1, y: p.y })
+}
+
+struct Point {
+    x: i64,
+    y: i64,
+}
+
+#[use_decorator(decorator())]
+fn sum((a, b): (i64, i64), Point { x, y }: Point, mut z: i64, _: u8) -> i64 {
+    z += 1;
+    a + b + x + y + z
+}
+
+#[use_decorator(point_decorator(), hide_parameters = [y])]
+fn add(Point { x, .. }: Point, mut y: i64) -> i64 {
+    y *= 10;
+    x + y
+}
+
+#[use_decorator(hiding_decorator(), exact_parameters = [y])]
+fn sub(_: Point, y: i64) -> i64 {
+    -y
+}
+
+struct MyStruct {
+    x: i64,
+}
+
+impl MyStruct {
+    #[use_impl_decorator(hiding_decorator(), exact_parameters = [y])]
+    fn add(&self, y: i64, [a, b]: [i64; 2]) -> i64 {
+        self.x + y + a + b
+    }
+}
+
+#[test]
+fn destructuring_params_decorator() {
+    assert_eq!(sum((1, 2), Point { x: 3, y: 4 }, 5, 0), 17);
+    assert_eq!(add(Point { x: 1, y: 0 }, 2), 22);
+    assert_eq!(sub(Point { x: 1, y: 0 }, 2), -3);
+
+    let obj = MyStruct { x: 1 };
+    assert_eq!(obj.add(1, [1, 1]), 5);
+}
@@ -0,0 +1,48 @@
+use fn_decorator::use_decorator;
+
+fn error_code_on_panic(f: impl FnOnce() -> i32 + std::panic::UnwindSafe) -> i32 {
+    std::panic::catch_unwind(f).unwrap_or(-1)
+}
+
+#[use_decorator(error_code_on_panic(), exact_parameters = [])]
+#[no_mangle]
+pub extern "C" fn fn_decorator_parse_digit(c: u8) -> i32 {
+    assert!(c.is_ascii_digit(), "not a digit");
+    (c - b'0').into()
+}
+
+#[use_decorator(error_code_on_panic(), exact_parameters = [])]
+#[unsafe(export_name = "fn_decorator_exported_double")]
+pub extern "C" fn double(x: i32) -> i32 {
+    x.checked_mul(2).expect("overflow")
+}
+
+// the exported symbols, as they are seen from C
+mod c_api {
+    extern "C" {
+        pub fn fn_decorator_parse_digit(c: u8) -> i32;
+        pub fn fn_decorator_exported_double(x: i32) -> i32;
+    }
+}
+
+#[test]
+fn exported_fn_decorator() {
+    // the exported symbols are the wrappers, so the panics are caught before reaching C
+    let (digit, not_digit) = unsafe {
+        (
+            c_api::fn_decorator_parse_digit(b'7'),
+            c_api::fn_decorator_parse_digit(b'x'),
+        )
+    };
+    assert_eq!(digit, 7);
+    assert_eq!(not_digit, -1);
+
+    let (doubled, overflown) = unsafe {
+        (
+            c_api::fn_decorator_exported_double(2),
+            c_api::fn_decorator_exported_double(i32::MAX),
+        )
+    };
+    assert_eq!(doubled, 4);
+    assert_eq!(overflown, -1);
+}
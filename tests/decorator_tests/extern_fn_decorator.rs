@@ -0,0 +1,17 @@
+use fn_decorator::use_decorator;
+
+fn decorator(f: fn(i64) -> i64, x: i64) -> i64 {
+    f(x) + 1
+}
+
+#[use_decorator(decorator())]
+extern "C" fn add_1(x: i64) -> i64 {
+    x + 1
+}
+
+#[test]
+fn extern_fn_decorator() {
+    let add_1: extern "C" fn(i64) -> i64 = add_1;
+    let result = add_1(1);
+    assert_eq!(result, 3);
+}
@@ -0,0 +1,50 @@
+use std::{
+    future::Future,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use fn_decorator::use_impl_decorator;
+
+static POLLS: AtomicU32 = AtomicU32::new(0);
+
+fn count_polls<FutureType: Future>(
+    f: impl FnOnce() -> FutureType,
+) -> impl Future<Output = FutureType::Output> {
+    let mut future = Box::pin(f());
+    std::future::poll_fn(move |cx| {
+        POLLS.fetch_add(1, Ordering::SeqCst);
+        future.as_mut().poll(cx)
+    })
+}
+
+struct Greeter(String);
+
+impl Greeter {
+    #[use_impl_decorator(count_polls(), exact_parameters = [], mode = future)]
+    async fn greet(&self, name: &str) -> String {
+        tokio::task::yield_now().await;
+        format!("{} {name}", self.0)
+    }
+
+    #[use_impl_decorator(count_polls(), exact_parameters = [], mode = future)]
+    async fn greeting(&self) -> &str {
+        &self.0
+    }
+}
+
+fn assert_send<T: Send>(value: T) -> T {
+    value
+}
+
+#[tokio::test]
+async fn future_returning_decorator() {
+    let greeter = Greeter("Hello".into());
+
+    let name = String::from("world");
+    let result = assert_send(greeter.greet(&name)).await;
+    assert_eq!(result, "Hello world");
+    assert_eq!(POLLS.load(Ordering::SeqCst), 2);
+
+    let result = greeter.greeting().await;
+    assert_eq!(result, "Hello");
+}
@@ -0,0 +1,89 @@
+use std::{fmt::Debug, future::Future};
+
+use fn_decorator::{decorator, use_decorator, use_impl_decorator};
+use fn_decorator_std::Next;
+
+#[decorator]
+fn describe<Args: Debug, R: Debug>(name: &str, next: Next<Args, R>) -> String {
+    let args = format!("{:?}", next.args());
+    format!("{name}{args} = {:?}", next.call())
+}
+
+#[decorator]
+fn increment_first<R>(mut next: Next<(i64, i64), R>) -> R {
+    next.args_mut().0 += 1;
+    next.call()
+}
+
+#[decorator]
+async fn describe_async<Args: Debug, R: Future>(next: Next<Args, R>) -> String
+where
+    R::Output: Debug,
+{
+    let args = format!("{:?}", next.args());
+    format!("{args} = {:?}", next.call().await)
+}
+
+#[use_decorator(describe("concat"), pack_parameters, override_return_type = String)]
+fn concat(left: String, right: &str) -> String {
+    left + right
+}
+
+#[use_decorator(describe("negate"), pack_parameters, override_return_type = String)]
+fn negate(x: bool) -> bool {
+    !x
+}
+
+#[use_decorator(describe("get_1"), pack_parameters, override_return_type = String)]
+fn get_1() -> i64 {
+    1
+}
+
+#[use_decorator(describe_async(), pack_parameters, override_return_type = String)]
+async fn double(x: i64) -> i64 {
+    x * 2
+}
+
+struct MyStruct {
+    x: i64,
+}
+
+impl Debug for MyStruct {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MyStruct({})", self.x)
+    }
+}
+
+impl MyStruct {
+    #[use_impl_decorator(describe("add"), pack_parameters, override_return_type = String)]
+    fn add(&self, y: i64) -> i64 {
+        self.x + y
+    }
+
+    #[use_impl_decorator(increment_first(), pack_parameters)]
+    fn sub(x: i64, y: i64) -> i64 {
+        x - y
+    }
+}
+
+#[test]
+fn generic_decorator() {
+    assert_eq!(
+        concat("left".into(), "right"),
+        r#"concat("left", "right") = "leftright""#
+    );
+    assert_eq!(negate(true), "negate(true,) = false");
+    assert_eq!(get_1(), "get_1() = 1");
+}
+
+#[test]
+fn generic_impl_decorator() {
+    let obj = MyStruct { x: 1 };
+    assert_eq!(obj.add(2), "add(MyStruct(1), 2) = 3");
+    assert_eq!(MyStruct::sub(1, 1), 1);
+}
+
+#[tokio::test]
+async fn generic_async_decorator() {
+    assert_eq!(double(2).await, "(2,) = 4");
+}
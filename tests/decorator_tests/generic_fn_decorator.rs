@@ -0,0 +1,97 @@
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+use fn_decorator::{use_decorator, use_impl_decorator};
+
+fn describe<T: Display>(f: fn(T) -> String, x: T) -> String {
+    format!("<{}>", f(x))
+}
+
+fn parse_or_default<R: Default>(f: fn(&str) -> Option<R>, s: &str) -> Option<R> {
+    Some(f(s).unwrap_or_default())
+}
+
+fn sum_decorator<const N: usize>(f: fn([i64; N]) -> i64, values: [i64; N]) -> i64 {
+    f(values) * 2
+}
+
+fn first_decorator<'a>(f: fn(&'a str, &'a str) -> &'a str, a: &'a str, b: &'a str) -> &'a str {
+    f(b, a)
+}
+
+fn identity<R>(f: impl FnOnce() -> R) -> R {
+    f()
+}
+
+fn debug_format<R: Debug>(f: impl FnOnce() -> R) -> String {
+    format!("{:?}", f())
+}
+
+#[use_decorator(describe())]
+fn to_string<T: Display>(x: T) -> String {
+    x.to_string()
+}
+
+#[use_decorator(parse_or_default())]
+fn parse<T>(s: &str) -> Option<T>
+where
+    T: FromStr + Default,
+{
+    s.parse().ok()
+}
+
+#[use_decorator(sum_decorator())]
+fn sum<const N: usize>(values: [i64; N]) -> i64 {
+    values.iter().sum()
+}
+
+#[use_decorator(first_decorator())]
+fn first<'a>(a: &'a str, _b: &'a str) -> &'a str {
+    a
+}
+
+#[use_decorator(identity(), exact_parameters = [])]
+fn repeat<T: Clone, const N: usize>(x: T) -> [T; N] {
+    std::array::from_fn(|_| x.clone())
+}
+
+#[use_decorator(debug_format(), exact_parameters = [], override_return_type = String)]
+fn default_array<T: Default + Debug, const N: usize>() -> [T; N] {
+    std::array::from_fn(|_| T::default())
+}
+
+struct MyStruct<T> {
+    x: T,
+}
+
+impl<T: Display> MyStruct<T> {
+    #[use_impl_decorator(identity(), exact_parameters = [])]
+    fn concat<U: Display>(&self, y: U) -> String {
+        format!("{}{}", self.x, y)
+    }
+
+    #[use_impl_decorator(identity(), exact_parameters = [])]
+    fn default_value<U: Default>() -> U {
+        U::default()
+    }
+}
+
+#[test]
+fn generic_fn_decorator() {
+    assert_eq!(to_string(1), "<1>");
+    assert_eq!(parse::<i64>("1"), Some(1));
+    assert_eq!(parse::<i64>("x"), Some(0));
+    assert_eq!(sum([1, 2, 3]), 12);
+    assert_eq!(first("a", "b"), "b");
+    assert_eq!(repeat::<_, 3>(1), [1, 1, 1]);
+    assert_eq!(default_array::<i64, 2>(), "[0, 0]");
+}
+
+#[test]
+fn generic_impl_member_decorator() {
+    let obj = MyStruct { x: 1 };
+    assert_eq!(obj.concat("a"), "1a");
+    assert_eq!(MyStruct::<i64>::default_value::<String>(), "");
+}
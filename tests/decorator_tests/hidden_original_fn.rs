@@ -0,0 +1,23 @@
+use fn_decorator::use_decorator;
+
+fn decorator(f: fn(i64) -> i64, x: i64) -> i64 {
+    f(x) + 1
+}
+
+#[use_decorator(decorator())]
+fn add_1(add_1_fn_decorator_original: i64) -> i64 {
+    add_1_fn_decorator_original + 1
+}
+
+fn add_1_fn_decorator_original(x: i64) -> i64 {
+    x + 10
+}
+
+#[test]
+fn hidden_original_fn() {
+    let result = add_1(1);
+    assert_eq!(result, 3);
+
+    let result = add_1_fn_decorator_original(1);
+    assert_eq!(result, 11);
+}
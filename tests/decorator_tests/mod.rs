@@ -1,14 +1,29 @@
+mod arguments_decorator;
 mod async_decorator;
 mod async_impl_member_decorator;
 mod async_impl_static_member_decorator;
+mod attributes_decorator;
+mod closure_decorator;
+mod conditional_decorator;
+mod context_decorator;
 mod debug_fn_decorator;
+mod decorate_impl;
+mod decorate_module;
+mod decorator_modes;
 mod decorator_with_param;
 mod decorator_without_params;
+mod destructuring_params_decorator;
 mod exact_params_of_async_fn_decorator;
 mod exact_params_of_async_impl_member_decorator;
 mod exact_params_of_fn_decorator;
 mod exact_params_of_impl_member_decorator;
+mod exported_fn_decorator;
+mod extern_fn_decorator;
 mod fn_without_params_decorator;
+mod future_returning_decorator;
+mod generic_decorator;
+mod generic_fn_decorator;
+mod hidden_original_fn;
 mod hiding_params_of_async_fn_decorator;
 mod hiding_params_of_async_impl_member_decorator;
 mod hiding_params_of_fn_decorator;
@@ -17,4 +32,12 @@ mod hiding_self_param_in_impl_member_decorator;
 mod impl_member_decorator;
 mod impl_static_member_decorator;
 mod overriding_return_type;
+mod packing_params_decorator;
+mod reusable_decorator;
+mod send_futures_decorator;
+mod stacked_decorators;
+mod switched_decorator;
+mod trait_impl_decorator;
+mod trait_method_declaration_decorator;
+mod unsafe_fn_decorator;
 mod visibility;
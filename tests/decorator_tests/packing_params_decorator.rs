@@ -0,0 +1,32 @@
+use std::fmt::Debug;
+
+use fn_decorator::use_decorator;
+
+fn describe<Args: Debug, R: Debug>(f: impl FnOnce(Args) -> R, args: Args) -> String {
+    let args_description = format!("{args:?}");
+    format!("{args_description} -> {:?}", f(args))
+}
+
+#[use_decorator(describe(), pack_parameters, override_return_type = String)]
+fn add(left: i64, right: i64) -> i64 {
+    left + right
+}
+
+#[use_decorator(
+    describe(),
+    pack_parameters,
+    hide_parameters = [password],
+    override_return_type = String,
+)]
+fn login(user: String, password: String) -> bool {
+    user == "admin" && password == "secret"
+}
+
+#[test]
+fn packing_params_decorator() {
+    let result = add(1, 2);
+    assert_eq!(result, "(1, 2) -> 3");
+
+    let result = login("admin".into(), "secret".into());
+    assert_eq!(result, r#"("admin",) -> true"#);
+}
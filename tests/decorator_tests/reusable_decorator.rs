@@ -0,0 +1,82 @@
+use std::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+
+use fn_decorator::{use_decorator, use_impl_decorator};
+use fn_decorator_std::{retry, retry_async, Arguments, Backoff};
+
+static ASYNC_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+
+#[use_decorator(
+    retry(Backoff::fixed(3, Duration::ZERO)),
+    exact_parameters = [],
+    reusable,
+)]
+fn fail_until(attempts: &mut u32, succeeding_attempt: u32, name: String) -> Result<String, u32> {
+    *attempts += 1;
+    if *attempts < succeeding_attempt {
+        Err(*attempts)
+    } else {
+        Ok(name)
+    }
+}
+
+#[use_decorator(
+    retry_async(Backoff::fixed(3, Duration::ZERO), tokio::time::sleep),
+    exact_parameters = [],
+    reusable,
+)]
+async fn fail_async(name: String) -> Result<(), String> {
+    ASYNC_ATTEMPTS.fetch_add(1, Ordering::SeqCst);
+    Err(name)
+}
+
+fn call_twice(f: impl Fn() -> String) -> String {
+    f() + &f()
+}
+
+fn call_twice_with_arguments(arguments: &Arguments, f: impl Fn() -> String) -> String {
+    format!("{arguments:?}: {}", call_twice(f))
+}
+
+struct Greeter(String);
+
+impl Greeter {
+    #[use_impl_decorator(call_twice(), hide_parameters = [self, name], reusable)]
+    fn greet(&self, name: String) -> String {
+        format!("{} {name}! ", self.0)
+    }
+
+    #[use_impl_decorator(
+        call_twice_with_arguments(),
+        with_arguments,
+        exact_parameters = [name],
+        reusable,
+    )]
+    fn greet_with_arguments(&self, name: String) -> String {
+        format!("{} {name}! ", self.0)
+    }
+}
+
+#[test]
+fn reusable_decorator() {
+    let mut attempts = 0;
+    let result = fail_until(&mut attempts, 2, "done".into());
+    assert_eq!(result, Ok("done".into()));
+    assert_eq!(attempts, 2);
+
+    let greeter = Greeter("Hello".into());
+    let result = greeter.greet("world".into());
+    assert_eq!(result, "Hello world! Hello world! ");
+
+    let result = greeter.greet_with_arguments("world".into());
+    assert_eq!(result, r#"[("name", "world")]: Hello world! Hello world! "#);
+}
+
+#[tokio::test]
+async fn reusable_async_decorator() {
+    let result = fail_async("failed".into()).await;
+    assert_eq!(result, Err("failed".into()));
+    assert_eq!(ASYNC_ATTEMPTS.load(Ordering::SeqCst), 3);
+}
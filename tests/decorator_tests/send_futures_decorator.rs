@@ -0,0 +1,85 @@
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
+
+use fn_decorator::{use_decorator, use_impl_decorator};
+
+async fn yield_before<FutureType: std::future::Future>(
+    f: impl FnOnce() -> FutureType,
+) -> FutureType::Output {
+    tokio::task::yield_now().await;
+    f().await
+}
+
+async fn add_one_later(f: fn(u32) -> u32, x: u32) -> u32 {
+    tokio::task::yield_now().await;
+    f(x) + 1
+}
+
+async fn add_twice<FutureType: std::future::Future<Output = u32>>(
+    f: impl FnOnce(u32) -> FutureType,
+    value: u32,
+) -> u32 {
+    tokio::task::yield_now().await;
+    f(value * 2).await
+}
+
+struct Counter {
+    count: AtomicU32,
+}
+
+impl Counter {
+    #[use_impl_decorator(yield_before(), exact_parameters = [], assert_send)]
+    async fn add(self: Arc<Self>, value: u32) -> u32 {
+        tokio::task::yield_now().await;
+        self.count.fetch_add(value, Ordering::SeqCst) + value
+    }
+
+    #[use_impl_decorator(add_twice(), hide_parameters = [self], assert_send)]
+    async fn add_hiding_self(self: Arc<Self>, value: u32) -> u32 {
+        tokio::task::yield_now().await;
+        self.count.fetch_add(value, Ordering::SeqCst) + value
+    }
+
+    #[use_impl_decorator(yield_before(), exact_parameters = [], assert_send)]
+    async fn add_static(&'static self, value: u32) -> u32 {
+        tokio::task::yield_now().await;
+        self.count.fetch_add(value, Ordering::SeqCst) + value
+    }
+}
+
+#[use_decorator(yield_before(), exact_parameters = [], assert_send)]
+#[use_decorator(yield_before(), exact_parameters = [])]
+async fn double(name: String) -> String {
+    tokio::task::yield_now().await;
+    name.repeat(2)
+}
+
+#[use_decorator(add_one_later(), mode = async, assert_send)]
+fn add_one(x: u32) -> u32 {
+    x
+}
+
+#[tokio::test]
+async fn send_futures_decorator() {
+    let counter = Arc::new(Counter {
+        count: AtomicU32::new(0),
+    });
+    let result = tokio::spawn(counter.clone().add(2)).await.unwrap();
+    assert_eq!(result, 2);
+    let result = tokio::spawn(counter.add_hiding_self(2)).await.unwrap();
+    assert_eq!(result, 6);
+
+    static COUNTER: Counter = Counter {
+        count: AtomicU32::new(0),
+    };
+    let result = tokio::spawn(COUNTER.add_static(3)).await.unwrap();
+    assert_eq!(result, 3);
+
+    let result = tokio::spawn(double("a".into())).await.unwrap();
+    assert_eq!(result, "aa");
+
+    let result = tokio::spawn(add_one(1)).await.unwrap();
+    assert_eq!(result, 2);
+}
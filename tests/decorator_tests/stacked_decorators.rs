@@ -0,0 +1,82 @@
+use fn_decorator::{use_decorator, use_impl_decorator};
+
+fn outer(f: fn(String) -> String, x: String) -> String {
+    format!("outer({})", f(x))
+}
+
+fn middle(name: &str, f: impl FnOnce() -> String) -> String {
+    format!("{name}({})", f())
+}
+
+fn inner(f: fn(String) -> String, x: String) -> Result<String, ()> {
+    Ok(format!("inner({})", f(x)))
+}
+
+fn unwrap_result(f: fn(String) -> Result<String, ()>, x: String) -> String {
+    f(x).unwrap()
+}
+
+#[use_decorator(
+    outer(),
+    middle("middle"),
+    exact_parameters = [],
+    unwrap_result(),
+    override_return_type = String,
+    inner(),
+    override_return_type = Result<String, ()>,
+)]
+fn stacked_in_one_attribute(x: String) -> String {
+    x
+}
+
+#[use_decorator(outer())]
+#[use_decorator(middle("middle"), exact_parameters = [])]
+#[use_decorator(unwrap_result(), override_return_type = String)]
+#[use_decorator(inner(), override_return_type = Result<String, ()>)]
+fn stacked_in_repeated_attributes(x: String) -> String {
+    x
+}
+
+fn add(f: fn(&MyStruct, i64) -> i64, receiver: &MyStruct, y: i64) -> i64 {
+    f(receiver, y) + 1
+}
+
+fn double(f: fn(&MyStruct, i64) -> i64, receiver: &MyStruct, y: i64) -> i64 {
+    f(receiver, y) * 2
+}
+
+struct MyStruct {
+    x: i64,
+}
+
+impl MyStruct {
+    #[use_impl_decorator(add(), double())]
+    fn add_then_double(&self, y: i64) -> i64 {
+        self.x + y
+    }
+
+    #[use_impl_decorator(double())]
+    #[use_impl_decorator(add())]
+    fn double_then_add(&self, y: i64) -> i64 {
+        self.x + y
+    }
+}
+
+#[test]
+fn stacked_decorators() {
+    assert_eq!(
+        stacked_in_one_attribute("x".into()),
+        "outer(middle(inner(x)))"
+    );
+    assert_eq!(
+        stacked_in_repeated_attributes("x".into()),
+        "outer(middle(inner(x)))"
+    );
+}
+
+#[test]
+fn stacked_impl_decorators() {
+    let obj = MyStruct { x: 1 };
+    assert_eq!(obj.add_then_double(1), 5);
+    assert_eq!(obj.double_then_add(1), 6);
+}
@@ -0,0 +1,88 @@
+use fn_decorator::{use_decorator, use_impl_decorator};
+use fn_decorator_std::{is_enabled, set_enabled, switch_names};
+
+fn add_one(f: fn(i64) -> i64, x: i64) -> i64 {
+    f(x) + 1
+}
+
+fn double(f: fn(i64) -> i64, x: i64) -> i64 {
+    f(x) * 2
+}
+
+async fn add_ten<FutureType: std::future::Future<Output = i64>>(
+    f: impl FnOnce() -> FutureType,
+) -> i64 {
+    f().await + 10
+}
+
+#[use_decorator(add_one(), switch)]
+fn get(x: i64) -> i64 {
+    x
+}
+
+#[use_decorator(double(), switch = "doubling", add_one(), switch = "adding")]
+fn get_stacked(x: i64) -> i64 {
+    x
+}
+
+struct Number(i64);
+
+impl Number {
+    #[use_impl_decorator(add_ten(), exact_parameters = [], switch = "async_adding")]
+    async fn add(&self, x: i64) -> i64 {
+        self.0 + x
+    }
+}
+
+// the switches of methods are named explicitly, so the same-named methods of different types
+// are switched separately
+struct A;
+
+impl A {
+    #[use_impl_decorator(add_one(), switch = "A::get")]
+    fn get(x: i64) -> i64 {
+        x
+    }
+}
+
+struct B;
+
+impl B {
+    #[use_impl_decorator(add_one(), switch = "B::get")]
+    fn get(x: i64) -> i64 {
+        x
+    }
+}
+
+#[tokio::test]
+async fn switched_decorator() {
+    let get_switch = concat!(module_path!(), "::get");
+    assert!(is_enabled(get_switch));
+    assert_eq!(get(1), 2);
+    set_enabled(get_switch, false);
+    assert_eq!(get(1), 1);
+    set_enabled(get_switch, true);
+    assert_eq!(get(1), 2);
+
+    assert_eq!(get_stacked(1), 4);
+    set_enabled("doubling", false);
+    assert_eq!(get_stacked(1), 2);
+    set_enabled("adding", false);
+    assert_eq!(get_stacked(1), 1);
+    set_enabled("doubling", true);
+    assert_eq!(get_stacked(1), 2);
+
+    // switches can be disabled before their first use
+    set_enabled("async_adding", false);
+    assert_eq!(Number(1).add(1).await, 2);
+    set_enabled("async_adding", true);
+    assert_eq!(Number(1).add(1).await, 12);
+
+    set_enabled("A::get", false);
+    assert_eq!(A::get(1), 1);
+    assert_eq!(B::get(1), 2);
+
+    let names = switch_names();
+    assert!(names.iter().any(|name| name == get_switch));
+    assert!(names.iter().any(|name| name == "doubling"));
+}
@@ -0,0 +1,148 @@
+use std::future::Future;
+
+use fn_decorator::decorate_impl;
+
+fn add_one(f: impl FnOnce() -> i64) -> i64 {
+    f() + 1
+}
+
+async fn add_one_async<FutureType: Future<Output = i64>>(f: impl FnOnce() -> FutureType) -> i64 {
+    f().await + 1
+}
+
+#[decorate_impl]
+trait Value {
+    fn value(&self) -> i64;
+
+    #[use_impl_decorator(add_one(), exact_parameters = [])]
+    fn double_value(&self) -> i64 {
+        self.value() * 2
+    }
+
+    #[use_impl_decorator(add_one_async(), exact_parameters = [])]
+    async fn triple_value(&self) -> i64 {
+        self.value() * 3
+    }
+}
+
+#[decorate_impl(add_one(), exact_parameters = [], include = [scaled])]
+trait Scale<T: Into<i64>> {
+    fn factor(&self) -> T;
+
+    fn scaled(&self, x: i64) -> i64 {
+        self.factor().into() * x
+    }
+}
+
+struct MyStruct {
+    x: i64,
+}
+
+impl Scale<i32> for MyStruct {
+    fn factor(&self) -> i32 {
+        2
+    }
+}
+
+#[decorate_impl(add_one(), exact_parameters = [])]
+impl Value for MyStruct {
+    fn value(&self) -> i64 {
+        self.x
+    }
+}
+
+struct Wrapper<T> {
+    x: T,
+}
+
+#[decorate_impl]
+impl<T: Into<i64> + Copy> Value for Wrapper<T> {
+    #[use_impl_decorator(add_one(), exact_parameters = [])]
+    fn value(&self) -> i64 {
+        self.x.into()
+    }
+}
+
+#[decorate_impl]
+impl<T> From<T> for Wrapper<T> {
+    #[use_impl_decorator(identity(), exact_parameters = [])]
+    #[use_impl_decorator(identity(), exact_parameters = [])]
+    fn from(x: T) -> Self {
+        Self { x }
+    }
+}
+
+fn identity<T>(f: impl FnOnce() -> T) -> T {
+    f()
+}
+
+struct Number(i64);
+
+fn increment(f: impl FnOnce() -> Number) -> Number {
+    Number(f().0 + 1)
+}
+
+// both impls have inner functions named after `from`
+#[decorate_impl(increment(), exact_parameters = [])]
+impl From<i32> for Number {
+    fn from(x: i32) -> Self {
+        Self(x.into())
+    }
+}
+
+#[decorate_impl(identity(), exact_parameters = [])]
+impl From<u8> for Number {
+    fn from(x: u8) -> Self {
+        Self(x.into())
+    }
+}
+
+// the inner functions are moved to a helper trait, so foreign types can be decorated too
+#[decorate_impl(add_one(), exact_parameters = [])]
+impl Value for i64 {
+    fn value(&self) -> i64 {
+        *self
+    }
+}
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+fn bracket(f: impl FnOnce() -> String) -> String {
+    format!("[{}]", f())
+}
+
+#[decorate_impl(bracket(), exact_parameters = [])]
+impl<T: std::fmt::Debug> Describe for T {
+    fn describe(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+#[test]
+fn trait_impl_decorator() {
+    let obj = MyStruct { x: 1 };
+    assert_eq!(obj.value(), 2);
+    assert_eq!(obj.double_value(), 5);
+    assert_eq!(obj.scaled(3), 7);
+
+    let obj = Wrapper::from(1i32);
+    assert_eq!(obj.value(), 2);
+    assert_eq!(obj.double_value(), 5);
+
+    assert_eq!(Number::from(1i32).0, 2);
+    assert_eq!(Number::from(1u8).0, 1);
+
+    assert_eq!(1i64.value(), 2);
+    assert_eq!(1i64.double_value(), 5);
+
+    assert_eq!(1.describe(), "[1]");
+    assert_eq!("x".describe(), "[\"x\"]");
+}
+
+#[tokio::test]
+async fn async_trait_default_method_decorator() {
+    let obj = MyStruct { x: 1 };
+    assert_eq!(obj.triple_value().await, 7);
+}
@@ -0,0 +1,61 @@
+use std::future::Future;
+
+use fn_decorator::{decorate_impl, use_impl_decorator};
+
+fn add_one(f: impl FnOnce() -> i64) -> i64 {
+    f() + 1
+}
+
+async fn double<FutureType: Future<Output = i64>>(f: impl FnOnce() -> FutureType) -> i64 {
+    f().await * 2
+}
+
+trait Account {
+    #[use_impl_decorator(add_one(), exact_parameters = [])]
+    fn balance(&self, offset: i64) -> i64;
+
+    #[use_impl_decorator(double(), exact_parameters = [])]
+    async fn fetch_balance(&self) -> i64;
+}
+
+// the inner layers of the stacked decorators are moved out of the trait by `decorate_impl`
+#[decorate_impl]
+trait Deposit {
+    #[use_impl_decorator(add_one(), exact_parameters = [])]
+    #[use_impl_decorator(add_one(), exact_parameters = [])]
+    fn deposit(&mut self, amount: i64) -> i64;
+}
+
+struct MyAccount {
+    balance: i64,
+}
+
+impl Account for MyAccount {
+    fn balance_impl(&self, offset: i64) -> i64 {
+        self.balance + offset
+    }
+
+    async fn fetch_balance_impl(&self) -> i64 {
+        self.balance
+    }
+}
+
+impl Deposit for MyAccount {
+    fn deposit_impl(&mut self, amount: i64) -> i64 {
+        self.balance += amount;
+        self.balance
+    }
+}
+
+#[test]
+fn trait_method_declaration_decorator() {
+    let mut account = MyAccount { balance: 1 };
+    assert_eq!(account.balance(1), 3);
+    assert_eq!(account.deposit(1), 4);
+}
+
+#[tokio::test]
+async fn async_trait_method_declaration_decorator() {
+    let account = MyAccount { balance: 1 };
+    assert_eq!(account.fetch_balance().await, 2);
+}
@@ -0,0 +1,36 @@
+use fn_decorator::{use_decorator, use_impl_decorator};
+
+fn decorator(f: unsafe fn(*const i64) -> i64, ptr: *const i64) -> i64 {
+    // SAFETY: the caller of the decorated function upholds the contract of `f`
+    unsafe { f(ptr) + 1 }
+}
+
+#[use_decorator(decorator())]
+unsafe fn read(ptr: *const i64) -> i64 {
+    *ptr
+}
+
+struct Pointer(*const i64);
+
+fn add_one(f: unsafe fn(&Pointer) -> i64, pointer: &Pointer) -> i64 {
+    // SAFETY: the caller of the decorated function upholds the contract of `f`
+    unsafe { f(pointer) + 1 }
+}
+
+impl Pointer {
+    #[use_impl_decorator(add_one())]
+    unsafe fn read(&self) -> i64 {
+        *self.0
+    }
+}
+
+#[test]
+fn unsafe_fn_decorator() {
+    let x = 1;
+
+    let result = unsafe { read(&x) };
+    assert_eq!(result, 2);
+
+    let result = unsafe { Pointer(&x).read() };
+    assert_eq!(result, 2);
+}
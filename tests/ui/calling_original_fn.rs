@@ -0,0 +1,14 @@
+use fn_decorator::use_decorator;
+
+fn decorator(f: fn() -> i64) -> i64 {
+    f() + 1
+}
+
+#[use_decorator(decorator())]
+fn get_1() -> i64 {
+    1
+}
+
+fn main() {
+    get_1_fn_decorator_original();
+}
@@ -0,0 +1,20 @@
+mod inner {
+    use fn_decorator::use_impl_decorator;
+
+    fn decorator(f: fn(&S) -> i64, s: &S) -> i64 {
+        f(s) + 1
+    }
+
+    pub struct S;
+
+    impl S {
+        #[use_impl_decorator(decorator())]
+        pub fn get(&self) -> i64 {
+            1
+        }
+    }
+}
+
+fn main() {
+    inner::S::get_fn_decorator_original(&inner::S);
+}
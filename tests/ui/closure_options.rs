@@ -0,0 +1,11 @@
+use fn_decorator::decorate;
+
+fn add_one(f: impl FnOnce(i64) -> i64, x: i64) -> i64 {
+    f(x) + 1
+}
+
+fn main() {
+    let _ = decorate!(add_one(), with_context, |x: i64| x);
+    let _ = decorate!(add_one(), hide_parameters = [y], |x: i64| x);
+    let _ = decorate!(add_one());
+}
@@ -0,0 +1,32 @@
+use fn_decorator::use_decorator;
+
+fn decorator(f: fn(i64) -> i64, x: i64) -> i64 {
+    f(x) + 1
+}
+
+#[use_decorator(exact_parameters = [x])]
+fn get_again(x: i64) -> i64 {
+    x
+}
+
+#[use_decorator(decorator(), debug, debug)]
+fn get_once_more(x: i64) -> i64 {
+    x
+}
+
+#[use_decorator(decorator(), with_arguments, pack_parameters)]
+fn get_packed(x: i64) -> i64 {
+    x
+}
+
+#[use_decorator(decorator(), when = cfg(all()), when = cfg(any()))]
+fn get_conditionally(x: i64) -> i64 {
+    x
+}
+
+#[use_decorator(decorator(), switch, override_return_type = i64)]
+fn get_switched(x: i64) -> i64 {
+    x
+}
+
+fn main() {}
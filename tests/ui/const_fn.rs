@@ -0,0 +1,12 @@
+use fn_decorator::use_decorator;
+
+fn decorator(f: fn() -> i64) -> i64 {
+    f() + 1
+}
+
+#[use_decorator(decorator())]
+const fn get_1() -> i64 {
+    1
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use fn_decorator::use_decorator;
+
+fn decorator(f: fn() -> i64) -> i64 {
+    f() + 1
+}
+
+extern "C" {
+    #[use_decorator(decorator())]
+    fn get_1() -> i64;
+}
+
+fn main() {}
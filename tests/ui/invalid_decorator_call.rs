@@ -0,0 +1,17 @@
+use fn_decorator::use_decorator;
+
+fn decorator(f: fn() -> i64) -> i64 {
+    f() + 1
+}
+
+#[use_decorator(decorator(,))]
+fn get_1() -> i64 {
+    1
+}
+
+#[use_decorator(self::decorator)]
+fn get_2() -> i64 {
+    2
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use fn_decorator::use_decorator;
+
+fn decorator(f: fn(i64) -> i64, x: i64) -> i64 {
+    f(x) + 1
+}
+
+#[use_decorator(decorator(), hide_parameter = [x])]
+fn get(x: i64) -> i64 {
+    x
+}
+
+#[use_decorator(decorator(), mode = synk)]
+fn get_again(x: i64) -> i64 {
+    x
+}
+
+fn main() {}
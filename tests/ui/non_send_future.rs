@@ -0,0 +1,15 @@
+use std::rc::Rc;
+
+use fn_decorator::use_decorator;
+
+async fn decorator<FutureType: std::future::Future>(f: impl FnOnce() -> FutureType) -> FutureType::Output {
+    f().await
+}
+
+#[use_decorator(decorator(), exact_parameters = [], assert_send)]
+async fn shared_len(name: Rc<String>) -> usize {
+    async {}.await;
+    name.len()
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use std::future::Future;
+
+use fn_decorator::use_decorator;
+
+async fn retry<F: Future<Output = Result<(), ()>>>(f: impl Fn() -> F) -> Result<(), ()> {
+    match f().await {
+        Ok(()) => Ok(()),
+        Err(()) => f().await,
+    }
+}
+
+#[use_decorator(retry(), exact_parameters = [], reusable)]
+async fn increment(counter: &mut u32) -> Result<(), ()> {
+    *counter += 1;
+    Err(())
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use fn_decorator::decorate_impl;
+
+fn decorator(f: fn() -> i64) -> i64 {
+    f() + 1
+}
+
+struct Numbers;
+
+#[decorate_impl(decorator(), exclude = [get_l])]
+impl Numbers {
+    fn get_1() -> i64 {
+        1
+    }
+}
+
+#[decorate_impl(decorator(), includ = [get_2])]
+impl Numbers {
+    fn get_2() -> i64 {
+        2
+    }
+}
+
+fn main() {}
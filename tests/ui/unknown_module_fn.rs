@@ -0,0 +1,17 @@
+use fn_decorator::decorate_module;
+
+#[decorate_module(super::add_one(), exact_parameters = [], exclude = [helpr, other_*])]
+mod api {
+    pub fn helper() -> i64 {
+        1
+    }
+}
+
+fn add_one(f: impl FnOnce() -> i64) -> i64 {
+    f() + 1
+}
+
+#[decorate_module(add_one(), exact_parameters = [], only = crate)]
+mod private {}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use fn_decorator::use_decorator;
+
+fn decorator(f: fn(i64, i64) -> i64, left: i64) -> i64 {
+    f(left, 0) + 1
+}
+
+#[use_decorator(decorator(), exact_parameters = [lef])]
+fn add(left: i64, right: i64) -> i64 {
+    left + right
+}
+
+#[use_decorator(decorator(), hide_parameters = [top])]
+fn sub(left: i64, right: i64) -> i64 {
+    left - right
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use fn_decorator::use_impl_decorator;
+
+fn decorator(f: fn() -> i64) -> i64 {
+    f() + 1
+}
+
+struct A;
+
+impl A {
+    #[use_impl_decorator(decorator(), switch)]
+    fn get() -> i64 {
+        1
+    }
+}
+
+fn main() {}
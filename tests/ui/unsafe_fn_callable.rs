@@ -0,0 +1,22 @@
+use fn_decorator::use_decorator;
+
+// a safe decorator function cannot receive an unsafe function
+fn safe_decorator(f: fn(*const i64) -> i64, _: *const i64) -> i64 {
+    f(std::ptr::null())
+}
+
+#[use_decorator(safe_decorator())]
+unsafe fn read(ptr: *const i64) -> i64 {
+    *ptr
+}
+
+fn add_one(f: impl FnOnce() -> i64) -> i64 {
+    f() + 1
+}
+
+#[use_decorator(add_one(), exact_parameters = [])]
+unsafe fn read_again(ptr: *const i64) -> i64 {
+    *ptr
+}
+
+fn main() {}
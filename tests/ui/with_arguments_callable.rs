@@ -0,0 +1,16 @@
+use fn_decorator::use_decorator;
+use fn_decorator_std::Arguments;
+
+fn call_twice(_arguments: &Arguments, f: impl Fn() -> String) -> String {
+    f();
+    f()
+}
+
+#[use_decorator(call_twice(), with_arguments)]
+fn greet(name: String) -> String {
+    format!("Hello, {name}!")
+}
+
+fn main() {
+    greet("World".into());
+}
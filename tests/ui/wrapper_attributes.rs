@@ -0,0 +1,36 @@
+#![deny(unused_must_use, deprecated)]
+
+use fn_decorator::{use_decorator, use_impl_decorator};
+
+fn decorator(f: fn(i64) -> i64, x: i64) -> i64 {
+    f(x) + 1
+}
+
+#[use_decorator(decorator())]
+#[must_use]
+fn get(x: i64) -> i64 {
+    x
+}
+
+#[use_decorator(decorator())]
+#[deprecated = "use `get` instead"]
+fn get_deprecated(x: i64) -> i64 {
+    x
+}
+
+struct Number;
+
+impl Number {
+    #[use_impl_decorator(decorator())]
+    #[must_use]
+    #[deprecated = "use `get` instead"]
+    fn get(x: i64) -> i64 {
+        x
+    }
+}
+
+fn main() {
+    get(1);
+    let _ = get_deprecated(1);
+    Number::get(1);
+}